@@ -11,10 +11,10 @@ fn main() -> std::io::Result<()> {
         .expect("Failed to open serial port");
 
     // Configure the CAN device bitrate.
-    serial.write(&format!("{}", Setup::new(Bitrate::Rate500kbit)).as_bytes())?;
+    serial.write_all(format!("{}", Setup::new(Bitrate::Rate500kbit)).as_bytes())?;
 
     // Open the connection.
-    serial.write(&format!("{}", Open::new()).as_bytes())?;
+    serial.write_all(format!("{}", Open::new()).as_bytes())?;
 
     // Send a single frame.
     let frame = Frame::new(
@@ -22,7 +22,7 @@ fn main() -> std::io::Result<()> {
         &[0, 1, 2, 3, 4, 5, 6, 7],
     )
     .unwrap();
-    serial.write(&format!("{}", Transmit::new(&frame)).as_bytes())?;
+    serial.write_all(format!("{}", Transmit::new(&frame)).as_bytes())?;
 
     Ok(())
 }
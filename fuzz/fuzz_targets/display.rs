@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use serial_can::{Bitrate, Command, Setup, Transmit};
+
+// Every valid command must encode to something that parses back to itself.
+fuzz_target!(|input: (Bitrate, Transmit)| {
+    let (bitrate, transmit) = input;
+
+    for command in [
+        Command::Setup(Setup::new(bitrate)),
+        Command::Transmit(transmit),
+    ] {
+        let encoded = command.to_string();
+        assert_eq!(Command::try_parse(&encoded), Ok(("", command)));
+    }
+});
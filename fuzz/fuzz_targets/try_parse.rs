@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use serial_can::Command;
+
+// Parsing arbitrary input must never panic, and anything that parses must
+// survive a round trip through its encoding.
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = core::str::from_utf8(data) else {
+        return;
+    };
+
+    if let Ok((_, command)) = Command::try_parse(input) {
+        let encoded = command.to_string();
+        assert_eq!(Command::try_parse(&encoded), Ok(("", command)));
+    }
+});
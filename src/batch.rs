@@ -0,0 +1,86 @@
+//! Fixed capacity command batches.
+
+use crate::{Command, EncodeError};
+
+/// An ordered batch of up to `N` commands.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CommandBatch<const N: usize>(heapless::Vec<Command, N>);
+
+impl<const N: usize> CommandBatch<N> {
+    pub fn new() -> Self {
+        Self(heapless::Vec::new())
+    }
+
+    /// Append a command, returning `false` if the batch is full.
+    pub fn push(&mut self, command: Command) -> bool {
+        self.0.push(command).is_ok()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> core::slice::Iter<'_, Command> {
+        self.0.iter()
+    }
+
+    /// Encode all commands one after another into `buf`, returning the number
+    /// of bytes written.
+    ///
+    /// Nothing is written if `buf` is too small for the whole batch.
+    pub fn encode_all(&self, buf: &mut [u8]) -> Result<usize, EncodeError> {
+        let needed = self.iter().map(|c| c.encoding_bytes().len()).sum();
+        if buf.len() < needed {
+            return Err(EncodeError::BufferTooSmall { needed });
+        }
+
+        let bytes = self.iter().flat_map(Command::encoding_bytes);
+        for (dest, byte) in buf.iter_mut().zip(bytes) {
+            *dest = byte;
+        }
+
+        Ok(needed)
+    }
+}
+
+impl<'a, const N: usize> IntoIterator for &'a CommandBatch<N> {
+    type Item = &'a Command;
+    type IntoIter = core::slice::Iter<'a, Command>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bitrate, Open, Setup};
+
+    #[test]
+    fn push_and_encode() {
+        let mut batch = CommandBatch::<2>::new();
+        assert!(batch.is_empty());
+        assert!(batch.push(Command::Setup(Setup::new(Bitrate::Rate500kbit))));
+        assert!(batch.push(Command::Open(Open::new())));
+        assert!(!batch.push(Command::Open(Open::new())));
+        assert_eq!(batch.len(), 2);
+        assert_eq!((&batch).into_iter().count(), 2);
+
+        let mut buf = [0; 8];
+        assert_eq!(batch.encode_all(&mut buf), Ok(5));
+        assert_eq!(&buf[..5], b"S6\rO\r");
+
+        let mut buf = [0; 4];
+        assert_eq!(
+            batch.encode_all(&mut buf),
+            Err(EncodeError::BufferTooSmall { needed: 5 })
+        );
+        assert_eq!(buf, [0; 4]);
+    }
+}
@@ -0,0 +1,120 @@
+//! Linux `candump` log parsing.
+
+use crate::{is_hex, Frame};
+use alloc::string::String;
+use embedded_can::{ExtendedId, Frame as _, Id, StandardId};
+
+/// Parse a line in `candump -L` log format.
+///
+/// Lines look like `(1234567890.123456) can0 123#DEADBEEF`. Standard IDs are
+/// written as 3 hex digits, as `candump` does, or 4 hex digits up to `07FF`.
+/// Extended IDs are written as 8 hex digits. Remote frames are written as
+/// `123#R`, optionally followed by the DLC.
+///
+/// Returns the timestamp in seconds, the interface name and the frame.
+pub fn parse_candump_line(line: &str) -> Option<(f64, String, Frame)> {
+    let mut fields = line.split_whitespace();
+
+    let timestamp = fields
+        .next()?
+        .strip_prefix('(')?
+        .strip_suffix(')')?
+        .parse()
+        .ok()?;
+    let interface = fields.next()?;
+    let (id, data) = fields.next()?.split_once('#')?;
+
+    if fields.next().is_some() || !is_hex(id) {
+        return None;
+    }
+
+    let id = match id.len() {
+        3 | 4 => Id::Standard(StandardId::new(u16::from_str_radix(id, 16).ok()?)?),
+        8 => Id::Extended(ExtendedId::new(u32::from_str_radix(id, 16).ok()?)?),
+        _ => return None,
+    };
+
+    let frame = if let Some(dlc) = data.strip_prefix('R') {
+        let dlc = match dlc {
+            "" => 0,
+            dlc if dlc.len() == 1 && is_hex(dlc) => usize::from_str_radix(dlc, 16).ok()?,
+            _ => return None,
+        };
+        Frame::new_remote(id, dlc)?
+    } else {
+        if data.len() % 2 != 0 || data.len() > 16 || !is_hex(data) {
+            return None;
+        }
+
+        let mut bytes = [0; 8];
+        let len = data.len() / 2;
+        for (i, byte) in bytes[..len].iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&data[i * 2..i * 2 + 2], 16).ok()?;
+        }
+        Frame::new(id, &bytes[..len])?
+    };
+
+    Some((timestamp, String::from(interface), frame))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_standard() {
+        let (timestamp, interface, frame) =
+            parse_candump_line("(1234567890.123456) can0 123#DEADBEEF").unwrap();
+
+        assert_eq!(timestamp, 1234567890.123456);
+        assert_eq!(interface, "can0");
+        assert_eq!(
+            frame,
+            Frame::new(StandardId::new(0x123).unwrap(), &[0xDE, 0xAD, 0xBE, 0xEF]).unwrap()
+        );
+
+        let (_, _, four_digits) =
+            parse_candump_line("(1234567890.123456) can0 0123#DEADBEEF").unwrap();
+        assert_eq!(four_digits, frame);
+
+        let (_, _, frame) = parse_candump_line("(0.0) can0 07FF#").unwrap();
+        assert_eq!(frame.id(), Id::Standard(StandardId::MAX));
+    }
+
+    #[test]
+    fn parse_extended() {
+        let (_, interface, frame) = parse_candump_line("(0.5) vcan1 12ABCDEF#").unwrap();
+
+        assert_eq!(interface, "vcan1");
+        assert_eq!(
+            frame,
+            Frame::new(ExtendedId::new(0x12ABCDEF).unwrap(), &[]).unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_remote() {
+        let id = StandardId::new(0x7DF).unwrap();
+
+        let (_, _, frame) = parse_candump_line("(0.0) can0 7DF#R").unwrap();
+        assert_eq!(frame, Frame::new_remote(id, 0).unwrap());
+
+        let (_, _, frame) = parse_candump_line("(0.0) can0 7DF#R8").unwrap();
+        assert_eq!(frame, Frame::new_remote(id, 8).unwrap());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(parse_candump_line("").is_none());
+        assert!(parse_candump_line("1.0 can0 123#00").is_none());
+        assert!(parse_candump_line("(1.0) can0 1234#00").is_none());
+        assert!(parse_candump_line("(1.0) can0 800#00").is_none());
+        assert!(parse_candump_line("(1.0) can0 0800#00").is_none());
+        assert!(parse_candump_line("(1.0) can0 12345#00").is_none());
+        assert!(parse_candump_line("(1.0) can0 123#0").is_none());
+        assert!(parse_candump_line("(1.0) can0 123#+1").is_none());
+        assert!(parse_candump_line("(1.0) can0 123#001122334455667788").is_none());
+        assert!(parse_candump_line("(1.0) can0 123#R9").is_none());
+        assert!(parse_candump_line("(1.0) can0 123#00 extra").is_none());
+    }
+}
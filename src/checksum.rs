@@ -0,0 +1,130 @@
+//! Checksums used on the CAN bus.
+
+use crate::Frame;
+use embedded_can::{Frame as _, Id};
+
+/// CAN CRC-15 generator polynomial.
+const CRC15_POLYNOMIAL: u16 = 0x4599;
+
+/// Compute the CAN CRC-15 of a bit sequence.
+pub fn crc15(bits: impl IntoIterator<Item = bool>) -> u16 {
+    bits.into_iter().fold(0, |crc, bit| {
+        let invert = bit ^ (crc & 0x4000 != 0);
+        let crc = (crc << 1) & 0x7FFF;
+
+        if invert {
+            crc ^ CRC15_POLYNOMIAL
+        } else {
+            crc
+        }
+    })
+}
+
+/// Unstuffed frame bits from start of frame to the end of the data field.
+struct FrameBits {
+    bits: u128,
+    len: u32,
+}
+
+impl FrameBits {
+    fn push(&mut self, value: u64, width: u32) {
+        self.bits = (self.bits << width) | value as u128;
+        self.len += width;
+    }
+
+    fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).rev().map(|i| self.bits & (1 << i) != 0)
+    }
+}
+
+impl Frame {
+    /// Compute the CRC-15 transmitted in this frame's CRC field.
+    ///
+    /// The CRC covers the start of frame, arbitration, control and data
+    /// fields before bit stuffing.
+    pub fn crc15(&self) -> u16 {
+        crc15(self.crc_bits().iter())
+    }
+
+    fn crc_bits(&self) -> FrameBits {
+        let mut bits = FrameBits { bits: 0, len: 0 };
+        let remote = self.is_remote_frame() as u64;
+
+        bits.push(0, 1); // start of frame
+
+        match self.id() {
+            Id::Standard(id) => {
+                bits.push(id.as_raw() as u64, 11);
+                bits.push(remote, 1);
+                bits.push(0, 1); // IDE
+                bits.push(0, 1); // r0
+            }
+            Id::Extended(id) => {
+                let id = id.as_raw() as u64;
+                bits.push(id >> 18, 11);
+                bits.push(1, 1); // SRR
+                bits.push(1, 1); // IDE
+                bits.push(id & 0x3FFFF, 18);
+                bits.push(remote, 1);
+                bits.push(0, 2); // r1, r0
+            }
+        }
+
+        bits.push(self.dlc() as u64, 4);
+
+        if self.is_data_frame() {
+            for byte in self.data() {
+                bits.push(*byte as u64, 8);
+            }
+        }
+
+        bits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_can::{ExtendedId, StandardId};
+
+    /// Appending the CRC to the bits it covers must leave no remainder.
+    fn assert_residue(frame: &Frame) {
+        let mut bits = frame.crc_bits();
+        bits.push(frame.crc15() as u64, 15);
+        assert_eq!(crc15(bits.iter()), 0);
+    }
+
+    #[test]
+    fn check_value() {
+        // Published CRC-15/CAN check value, with each byte sent MSB first.
+        let bits = b"123456789"
+            .iter()
+            .flat_map(|byte| (0..8).rev().map(move |i| byte >> i & 1 != 0));
+        assert_eq!(crc15(bits), 0x059E);
+    }
+
+    #[test]
+    fn all_zero_frame() {
+        let frame = Frame::new(Id::Standard(StandardId::ZERO), &[]).unwrap();
+        assert_eq!(frame.crc15(), 0);
+    }
+
+    #[test]
+    fn frame_residue() {
+        let standard = Id::Standard(StandardId::new(0x123).unwrap());
+        let extended = Id::Extended(ExtendedId::new(0x12ABCDEF).unwrap());
+
+        assert_residue(&Frame::new(standard, &[0xAA, 0x55]).unwrap());
+        assert_residue(&Frame::new(extended, &[0xFF; 8]).unwrap());
+        assert_residue(&Frame::new_remote(standard, 8).unwrap());
+        assert_residue(&Frame::new_remote(extended, 0).unwrap());
+    }
+
+    #[test]
+    fn remote_frame_excludes_data() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+        let remote = Frame::new_remote(id, 2).unwrap();
+        let data = Frame::new(id, &[0, 0]).unwrap();
+        assert_ne!(remote.crc15(), data.crc15());
+    }
+}
@@ -0,0 +1,115 @@
+//! Compile time checked command sequencing.
+//!
+//! [`SlcanDevice`] tracks whether the channel is open in its type, so sending
+//! commands out of order, such as transmitting before the channel is open or
+//! opening it twice, fails to compile. For checks at run time see
+//! [`crate::SlcanCommandSequencer`].
+
+use core::marker::PhantomData;
+
+use crate::{Bitrate, Close, Frame, OpenListenOnly, Setup, Transmit};
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for super::Closed {}
+    impl Sealed for super::Open {}
+    impl Sealed for super::ListenOnly {}
+}
+
+/// The channel is closed and can be set up.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Closed;
+
+/// The channel is open for sending and receiving.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Open;
+
+/// The channel is open for receiving only.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ListenOnly;
+
+/// States in which the channel is open and can be closed.
+pub trait Opened: sealed::Sealed {}
+
+impl Opened for Open {}
+impl Opened for ListenOnly {}
+
+/// An slcan adapter in state `S`.
+///
+/// Each transition consumes the device and returns it in the new state along
+/// with the command to send.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SlcanDevice<S>(PhantomData<S>);
+
+impl SlcanDevice<Closed> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+
+    /// Command setting the bitrate to use when the channel is next opened.
+    pub fn setup(&self, bitrate: Bitrate) -> Setup {
+        Setup::new(bitrate)
+    }
+
+    /// Open the channel.
+    pub fn open(self) -> (SlcanDevice<Open>, crate::Open) {
+        (SlcanDevice(PhantomData), crate::Open::new())
+    }
+
+    /// Open the channel without acknowledging or sending frames.
+    pub fn open_listen_only(self) -> (SlcanDevice<ListenOnly>, OpenListenOnly) {
+        (SlcanDevice(PhantomData), OpenListenOnly::new())
+    }
+}
+
+impl Default for SlcanDevice<Closed> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SlcanDevice<Open> {
+    /// Command sending `frame` on the bus.
+    pub fn transmit(&self, frame: &Frame) -> Transmit {
+        Transmit::new(frame)
+    }
+}
+
+impl<S: Opened> SlcanDevice<S> {
+    /// Close the channel.
+    pub fn close(self) -> (SlcanDevice<Closed>, Close) {
+        (SlcanDevice(PhantomData), Close::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_can::{Frame as _, StandardId};
+
+    #[test]
+    fn open_transmit_close() {
+        let device = SlcanDevice::new();
+        assert_eq!(format!("{}", device.setup(Bitrate::Rate500kbit)), "S6\r");
+
+        let (device, open) = device.open();
+        assert_eq!(format!("{}", open), "O\r");
+
+        let frame = Frame::new(StandardId::new(0x123).unwrap(), &[0xAA]).unwrap();
+        assert_eq!(format!("{}", device.transmit(&frame)), "t1231AA\r");
+
+        let (device, close) = device.close();
+        assert_eq!(format!("{}", close), "C\r");
+
+        let (device, open) = device.open_listen_only();
+        assert_eq!(format!("{}", open), "L\r");
+
+        let (_, close) = device.close();
+        assert_eq!(close, Close::new());
+    }
+}
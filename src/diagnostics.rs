@@ -0,0 +1,86 @@
+//! OBD-II diagnostics helpers.
+
+use crate::{Frame, Transmit};
+use embedded_can::{Frame as _, Id, StandardId};
+
+/// OBD-II functional (broadcast) request address.
+pub const OBD2_BROADCAST_ID: u16 = 0x7DF;
+
+/// OBD-II functional (broadcast) request address for 29-bit identifiers.
+pub const OBD2_EXTENDED_BROADCAST_ID: u32 = 0x18DB33F1;
+
+/// Create a [`Transmit`] sending `data` to the OBD-II broadcast address.
+///
+/// OBD-II frames are always 8 bytes long, so shorter data is zero padded.
+/// Returns `None` if `data` is longer than 8 bytes.
+pub fn new_obd2_broadcast(data: &[u8]) -> Option<Transmit> {
+    if data.len() > 8 {
+        return None;
+    }
+
+    let mut padded = [0; 8];
+    padded[..data.len()].copy_from_slice(data);
+
+    let id = StandardId::new(OBD2_BROADCAST_ID).unwrap();
+    let frame = Frame::new(id, &padded)?;
+
+    Some(Transmit::new(&frame))
+}
+
+impl Transmit {
+    /// Check if the frame is sent to the OBD-II broadcast address, either
+    /// `0x7DF` or the 29-bit `0x18DB33F1`.
+    pub fn is_broadcast(&self) -> bool {
+        match self.frame.id() {
+            Id::Standard(id) => id.as_raw() == OBD2_BROADCAST_ID,
+            Id::Extended(id) => id.as_raw() == OBD2_EXTENDED_BROADCAST_ID,
+        }
+    }
+
+    /// Check if the frame is an OBD-II response from an ECU, with a standard
+    /// identifier from `0x7E8` to `0x7EF`.
+    pub fn is_obd2_response(&self) -> bool {
+        match self.frame.id() {
+            Id::Standard(id) => (0x7E8..=0x7EF).contains(&id.as_raw()),
+            Id::Extended(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_can::ExtendedId;
+
+    #[test]
+    fn obd2_broadcast() {
+        // Request engine RPM, mode 01 PID 0C.
+        let transmit = new_obd2_broadcast(&[0x02, 0x01, 0x0C]).unwrap();
+        assert_eq!(format!("{}", transmit), "t7DF802010C0000000000\r");
+
+        assert!(new_obd2_broadcast(&[0; 8]).is_some());
+        assert!(new_obd2_broadcast(&[0; 9]).is_none());
+    }
+
+    #[test]
+    fn broadcast() {
+        assert!(new_obd2_broadcast(&[]).unwrap().is_broadcast());
+
+        let id = ExtendedId::new(OBD2_EXTENDED_BROADCAST_ID).unwrap();
+        assert!(Transmit::new(&Frame::new(id, &[]).unwrap()).is_broadcast());
+
+        let id = ExtendedId::new(0x7DF).unwrap();
+        assert!(!Transmit::new(&Frame::new(id, &[]).unwrap()).is_broadcast());
+    }
+
+    #[test]
+    fn obd2_response() {
+        for (raw, response) in [(0x7E7, false), (0x7E8, true), (0x7EF, true), (0x7F0, false)] {
+            let frame = Frame::new(StandardId::new(raw).unwrap(), &[]).unwrap();
+            assert_eq!(Transmit::new(&frame).is_obd2_response(), response);
+        }
+
+        let frame = Frame::new(ExtendedId::new(0x7E8).unwrap(), &[]).unwrap();
+        assert!(!Transmit::new(&frame).is_obd2_response());
+    }
+}
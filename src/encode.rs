@@ -0,0 +1,216 @@
+//! Command encoding without allocation.
+
+use core::fmt::{Display, Write};
+
+use embedded_can::{Frame as _, Id};
+
+use crate::{Close, Command, EncodeError, Open, Setup, Transmit, MAX_COMMAND_LEN};
+
+/// [`core::fmt::Write`] into a byte slice.
+pub(crate) struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    pub(crate) fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, len: 0 }
+    }
+
+    /// Number of bytes written so far.
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.len + s.len();
+        let dest = self.buf.get_mut(self.len..end).ok_or(core::fmt::Error)?;
+        dest.copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Iterator over the encoded bytes of a command.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EncodingBytes {
+    buf: [u8; MAX_COMMAND_LEN],
+    len: usize,
+    position: usize,
+}
+
+impl EncodingBytes {
+    pub(crate) fn new(command: &impl Display) -> Self {
+        let mut buf = [0; MAX_COMMAND_LEN];
+        let mut writer = SliceWriter::new(&mut buf);
+
+        // Every command fits in the buffer so this cannot fail.
+        write!(writer, "{}", command).unwrap();
+        let len = writer.len();
+
+        Self {
+            buf,
+            len,
+            position: 0,
+        }
+    }
+}
+
+impl Iterator for EncodingBytes {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.position == self.len {
+            return None;
+        }
+
+        let byte = self.buf[self.position];
+        self.position += 1;
+        Some(byte)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.position;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for EncodingBytes {}
+
+/// Encode a command straight into a byte slice.
+pub trait EncodeCommand {
+    /// Encode the command into the start of `buf`, returning the number of
+    /// bytes written.
+    ///
+    /// Nothing is written if `buf` is shorter than [`Self::encoded_len`].
+    fn encode_to_slice(&self, buf: &mut [u8]) -> Result<usize, EncodeError>;
+
+    /// Length of the encoded command, including the `\r` terminator.
+    fn encoded_len(&self) -> usize;
+}
+
+/// The first `len` bytes of `buf`, if it is long enough.
+fn prefix(buf: &mut [u8], len: usize) -> Result<&mut [u8], EncodeError> {
+    buf.get_mut(..len)
+        .ok_or(EncodeError::BufferTooSmall { needed: len })
+}
+
+/// Uppercase ASCII hex digit for the low nibble of `value`.
+fn hex_digit(value: u32) -> u8 {
+    b"0123456789ABCDEF"[(value & 0xF) as usize]
+}
+
+impl EncodeCommand for Setup {
+    fn encode_to_slice(&self, buf: &mut [u8]) -> Result<usize, EncodeError> {
+        let buf = prefix(buf, self.encoded_len())?;
+        buf[0] = b'S';
+        buf[1] = hex_digit(u8::from(self.bitrate) as u32);
+        buf[2] = b'\r';
+        Ok(buf.len())
+    }
+
+    fn encoded_len(&self) -> usize {
+        3
+    }
+}
+
+impl EncodeCommand for Open {
+    fn encode_to_slice(&self, buf: &mut [u8]) -> Result<usize, EncodeError> {
+        let buf = prefix(buf, self.encoded_len())?;
+        buf[0] = b'O';
+        buf[1] = b'\r';
+        Ok(buf.len())
+    }
+
+    fn encoded_len(&self) -> usize {
+        2
+    }
+}
+
+impl EncodeCommand for Close {
+    fn encode_to_slice(&self, buf: &mut [u8]) -> Result<usize, EncodeError> {
+        let buf = prefix(buf, self.encoded_len())?;
+        buf[0] = b'C';
+        buf[1] = b'\r';
+        Ok(buf.len())
+    }
+
+    fn encoded_len(&self) -> usize {
+        2
+    }
+}
+
+impl EncodeCommand for Transmit {
+    fn encode_to_slice(&self, buf: &mut [u8]) -> Result<usize, EncodeError> {
+        let buf = prefix(buf, self.encoded_len())?;
+        let frame = self.frame();
+        let remote = frame.is_remote_frame();
+
+        let (letter, id, id_len) = match frame.id() {
+            Id::Standard(id) => (if remote { b'r' } else { b't' }, id.as_raw() as u32, 3),
+            Id::Extended(id) => (if remote { b'R' } else { b'T' }, id.as_raw(), 8),
+        };
+
+        buf[0] = letter;
+        for i in 0..id_len {
+            buf[1 + i] = hex_digit(id >> (4 * (id_len - 1 - i)));
+        }
+        buf[1 + id_len] = hex_digit(frame.dlc() as u32);
+
+        if !remote {
+            for (i, byte) in frame.data().iter().enumerate() {
+                buf[2 + id_len + 2 * i] = hex_digit(*byte as u32 >> 4);
+                buf[3 + id_len + 2 * i] = hex_digit(*byte as u32);
+            }
+        }
+
+        buf[buf.len() - 1] = b'\r';
+        Ok(buf.len())
+    }
+
+    fn encoded_len(&self) -> usize {
+        let frame = self.frame();
+        let id_len = if frame.is_extended() { 8 } else { 3 };
+        let data_len = if frame.is_remote_frame() {
+            0
+        } else {
+            frame.dlc() * 2
+        };
+
+        id_len + data_len + 3
+    }
+}
+
+/// Commands other than [`Setup`], [`Open`], [`Close`] and [`Transmit`] are
+/// copied from [`EncodingBytes`].
+impl EncodeCommand for Command {
+    fn encode_to_slice(&self, buf: &mut [u8]) -> Result<usize, EncodeError> {
+        match self {
+            Command::Setup(setup) => setup.encode_to_slice(buf),
+            Command::Open(open) => open.encode_to_slice(buf),
+            Command::Close(close) => close.encode_to_slice(buf),
+            Command::Transmit(transmit) => transmit.encode_to_slice(buf),
+            _ => {
+                let bytes = self.encoding_bytes();
+                let buf = prefix(buf, bytes.len())?;
+                for (dest, byte) in buf.iter_mut().zip(bytes) {
+                    *dest = byte;
+                }
+                Ok(buf.len())
+            }
+        }
+    }
+
+    fn encoded_len(&self) -> usize {
+        match self {
+            Command::Setup(setup) => setup.encoded_len(),
+            Command::Open(open) => open.encoded_len(),
+            Command::Close(close) => close.encoded_len(),
+            Command::Transmit(transmit) => transmit.encoded_len(),
+            _ => self.encoding_bytes().len(),
+        }
+    }
+}
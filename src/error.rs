@@ -0,0 +1,146 @@
+//! Error types.
+
+/// Errors from sending slcan commands.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SlcanError {
+    /// Formatting the command failed.
+    Format,
+    /// The command is not valid in the current adapter state.
+    InvalidCommandForState,
+    /// The underlying transport failed.
+    #[cfg(feature = "embedded-io")]
+    Io(embedded_io::ErrorKind),
+    /// Another error labelled with where it happened, see
+    /// [`SlcanError::context`].
+    #[cfg(feature = "alloc")]
+    WithContext {
+        inner: alloc::boxed::Box<SlcanError>,
+        context: &'static str,
+    },
+}
+
+impl SlcanError {
+    /// Label the error with where it happened, for example the operation that
+    /// was being attempted.
+    #[cfg(feature = "alloc")]
+    pub fn context(self, context: &'static str) -> Self {
+        SlcanError::WithContext {
+            inner: alloc::boxed::Box::new(self),
+            context,
+        }
+    }
+}
+
+impl core::fmt::Display for SlcanError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SlcanError::Format => write!(f, "Failed to format slcan command"),
+            SlcanError::InvalidCommandForState => {
+                write!(f, "Command not valid in current adapter state")
+            }
+            #[cfg(feature = "embedded-io")]
+            SlcanError::Io(kind) => write!(f, "Transport error ({:?})", kind),
+            #[cfg(feature = "alloc")]
+            SlcanError::WithContext { inner, context } => write!(f, "{}: {}", context, inner),
+        }
+    }
+}
+
+// Derived bounds would recurse through `WithContext`.
+#[cfg(feature = "defmt")]
+impl defmt::Format for SlcanError {
+    fn format(&self, f: defmt::Formatter) {
+        match self {
+            SlcanError::Format => defmt::write!(f, "Format"),
+            SlcanError::InvalidCommandForState => defmt::write!(f, "InvalidCommandForState"),
+            #[cfg(feature = "embedded-io")]
+            SlcanError::Io(kind) => defmt::write!(f, "Io({})", kind),
+            #[cfg(feature = "alloc")]
+            SlcanError::WithContext { inner, context } => defmt::write!(
+                f,
+                "WithContext {{ inner: {}, context: {=str} }}",
+                inner.as_ref(),
+                context
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<E: embedded_io::Error> From<embedded_io::WriteFmtError<E>> for SlcanError {
+    fn from(err: embedded_io::WriteFmtError<E>) -> Self {
+        match err {
+            embedded_io::WriteFmtError::FmtError => SlcanError::Format,
+            embedded_io::WriteFmtError::Other(err) => SlcanError::Io(err.kind()),
+        }
+    }
+}
+
+/// Errors from encoding commands into a buffer.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EncodeError {
+    /// The buffer is shorter than the `needed` number of bytes.
+    BufferTooSmall { needed: usize },
+}
+
+impl core::fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            EncodeError::BufferTooSmall { needed } => write!(
+                f,
+                "Buffer too small for encoded command ({} bytes needed)",
+                needed
+            ),
+        }
+    }
+}
+
+/// Errors from parsing slcan commands.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ParseError {
+    /// A field contains a character that is not a hex digit.
+    InvalidHex,
+    /// The identifier is not 3 (standard) or 8 (extended) hex digits long.
+    InvalidIdLength,
+    /// The identifier is out of range for its frame type.
+    InvalidId,
+    /// The data is longer than 8 bytes or has an odd number of hex digits.
+    InvalidDataLength,
+    /// The timestamp is not 4 hex digits long.
+    InvalidTimestampLength,
+    /// The input does not contain a complete `\r` terminated command yet.
+    Incomplete,
+    /// The command letter is not one this parser accepts.
+    UnknownCommand,
+    /// The command letter is known but the rest of the command is malformed.
+    InvalidCommand,
+    /// The buffer filled up before a `\r` terminator was received.
+    BufferOverflow,
+}
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseError::InvalidHex => write!(f, "Expected only hex digits"),
+            ParseError::InvalidIdLength => {
+                write!(f, "CAN ID must be 3 (standard) or 8 (extended) hex digits")
+            }
+            ParseError::InvalidId => write!(f, "CAN ID out of range for frame type"),
+            ParseError::InvalidDataLength => write!(
+                f,
+                "Frame data must be an even number of hex digits, at most 16"
+            ),
+            ParseError::InvalidTimestampLength => {
+                write!(f, "Timestamp must be 4 hex digits")
+            }
+            ParseError::Incomplete => write!(f, "Command is not terminated yet"),
+            ParseError::UnknownCommand => write!(f, "Unknown command letter"),
+            ParseError::InvalidCommand => write!(f, "Malformed command"),
+            ParseError::BufferOverflow => {
+                write!(f, "Buffer full before command was terminated")
+            }
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! CAN FD frames.
+
+use crate::{hex_number, write_hex};
+use embedded_can::{ExtendedId, Id, StandardId};
+use nom::{
+    bytes::complete::tag,
+    character::complete::one_of,
+    error::{Error, ErrorKind},
+    Err, IResult,
+};
+
+/// Payload lengths for DLC codes 0 to 15.
+const DLC_BYTES: [usize; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 16, 20, 24, 32, 48, 64];
+
+/// Conversions between CAN FD DLC codes and payload lengths.
+///
+/// Codes 0 to 8 are the payload length, codes 9 to 15 stand for 12, 16, 20,
+/// 24, 32, 48 and 64 bytes.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DlcCode;
+
+impl DlcCode {
+    /// Smallest DLC code whose payload holds `n` bytes, or `None` if `n` is
+    /// more than 64.
+    pub fn from_bytes(n: usize) -> Option<u8> {
+        DLC_BYTES
+            .iter()
+            .position(|&len| len >= n)
+            .map(|code| code as u8)
+    }
+
+    /// Payload length for a DLC code. Codes above 15 are treated as 15.
+    pub fn to_bytes(dlc: u8) -> usize {
+        DLC_BYTES[dlc.min(15) as usize]
+    }
+}
+
+/// CAN FD data frame carrying up to 64 bytes.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct CanFdFrame {
+    id: Id,
+    dlc: u8,
+    data: [u8; 64],
+}
+
+impl CanFdFrame {
+    /// Create a frame, zero padding `data` up to the next valid FD payload
+    /// length. Returns `None` if `data` is longer than 64 bytes.
+    pub fn new(id: impl Into<Id>, data: &[u8]) -> Option<Self> {
+        let dlc = DlcCode::from_bytes(data.len())?;
+        let mut bytes = [0; 64];
+        bytes[..data.len()].copy_from_slice(data);
+
+        Some(Self {
+            id: id.into(),
+            dlc,
+            data: bytes,
+        })
+    }
+
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    pub fn is_extended(&self) -> bool {
+        matches!(self.id, Id::Extended(_))
+    }
+
+    /// DLC code, 0 to 15.
+    pub fn dlc(&self) -> u8 {
+        self.dlc
+    }
+
+    /// Payload, [`DlcCode::to_bytes`] long.
+    pub fn data(&self) -> &[u8] {
+        &self.data[..DlcCode::to_bytes(self.dlc)]
+    }
+}
+
+/// Only the payload is printed, not the padding.
+#[cfg(feature = "defmt")]
+impl defmt::Format for CanFdFrame {
+    fn format(&self, f: defmt::Formatter) {
+        let (id, extended) = match self.id {
+            Id::Standard(id) => (id.as_raw() as u32, false),
+            Id::Extended(id) => (id.as_raw(), true),
+        };
+
+        defmt::write!(
+            f,
+            "CanFdFrame {{ id: {=u32:#x}, extended: {=bool}, dlc: {=u8}, data: {=[u8]:#x} }}",
+            id,
+            extended,
+            self.dlc,
+            self.data(),
+        )
+    }
+}
+
+/// Transmit CAN FD frame command, `d` for standard and `D` for extended
+/// identifiers.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TransmitFd {
+    frame: CanFdFrame,
+}
+
+impl TransmitFd {
+    pub fn new(frame: &CanFdFrame) -> Self {
+        Self { frame: *frame }
+    }
+
+    pub fn frame(&self) -> &CanFdFrame {
+        &self.frame
+    }
+
+    /// Try parsing a [`TransmitFd`] command from a string.
+    pub fn try_parse(input: &str) -> IResult<&str, Self> {
+        let (input, kind) = one_of("dD")(input)?;
+        let (input, id) = if kind == 'd' {
+            let (rest, id) = hex_number(3)(input)?;
+            let id = StandardId::new(id as u16)
+                .ok_or(Err::Failure(Error::new(input, ErrorKind::Verify)))?;
+            (rest, Id::Standard(id))
+        } else {
+            let (rest, id) = hex_number(8)(input)?;
+            let id =
+                ExtendedId::new(id).ok_or(Err::Failure(Error::new(input, ErrorKind::Verify)))?;
+            (rest, Id::Extended(id))
+        };
+
+        let (mut input, dlc) = hex_number(1)(input)?;
+        let dlc = dlc as u8;
+
+        let mut data = [0; 64];
+        for byte in &mut data[..DlcCode::to_bytes(dlc)] {
+            let (rest, value) = hex_number(2)(input)?;
+            *byte = value as u8;
+            input = rest;
+        }
+
+        let (input, _) = tag("\r")(input)?;
+
+        Ok((
+            input,
+            Self {
+                frame: CanFdFrame { id, dlc, data },
+            },
+        ))
+    }
+}
+
+impl core::fmt::Display for TransmitFd {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.frame.id {
+            Id::Standard(id) => {
+                write!(f, "d")?;
+                write_hex(f, id.as_raw().into(), 3, false)?;
+            }
+            Id::Extended(id) => {
+                write!(f, "D")?;
+                write_hex(f, id.as_raw(), 8, false)?;
+            }
+        }
+
+        write_hex(f, self.frame.dlc.into(), 1, false)?;
+
+        for byte in self.frame.data() {
+            write_hex(f, (*byte).into(), 2, false)?;
+        }
+
+        write!(f, "\r")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dlc_codes() {
+        for (dlc, len) in DLC_BYTES.iter().enumerate() {
+            assert_eq!(DlcCode::to_bytes(dlc as u8), *len);
+            assert_eq!(DlcCode::from_bytes(*len), Some(dlc as u8));
+        }
+
+        assert_eq!(DlcCode::from_bytes(9), Some(9));
+        assert_eq!(DlcCode::from_bytes(33), Some(14));
+        assert_eq!(DlcCode::from_bytes(65), None);
+        assert_eq!(DlcCode::to_bytes(16), 64);
+    }
+
+    #[test]
+    fn frame_padding() {
+        let frame = CanFdFrame::new(StandardId::ZERO, &[1; 10]).unwrap();
+        assert_eq!(frame.dlc(), 9);
+        assert_eq!(frame.data(), &[1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0]);
+
+        assert!(CanFdFrame::new(StandardId::ZERO, &[0; 65]).is_none());
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let frame = CanFdFrame::new(StandardId::new(0x123).unwrap(), &[0xAA; 12]).unwrap();
+        let command = TransmitFd::new(&frame);
+        let encoded = "d1239AAAAAAAAAAAAAAAAAAAAAAAA\r";
+        assert_eq!(format!("{}", command), encoded);
+        assert_eq!(TransmitFd::try_parse(encoded), Ok(("", command)));
+
+        let data: Vec<u8> = (0..64).collect();
+        let frame = CanFdFrame::new(ExtendedId::MAX, &data).unwrap();
+        let command = TransmitFd::new(&frame);
+        let encoded = format!("{}", command);
+        assert!(encoded.starts_with("D1FFFFFFFF000102"));
+        assert_eq!(encoded.len(), 1 + 8 + 1 + 128 + 1);
+        assert_eq!(TransmitFd::try_parse(&encoded), Ok(("", command)));
+
+        let frame = CanFdFrame::new(StandardId::ZERO, &[]).unwrap();
+        assert_eq!(
+            TransmitFd::try_parse("d0000\r"),
+            Ok(("", TransmitFd::new(&frame)))
+        );
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(TransmitFd::try_parse("t1230\r").is_err());
+        assert!(TransmitFd::try_parse("d8000\r").is_err());
+        assert!(TransmitFd::try_parse("d1239AA\r").is_err());
+        assert!(TransmitFd::try_parse("d1232AA55").is_err());
+        assert!(TransmitFd::try_parse("d1232A+55\r").is_err());
+    }
+}
@@ -0,0 +1,199 @@
+//! Fixed layout frames for passing to and from C code.
+
+use crate::Frame;
+use embedded_can::{ExtendedId, Frame as _, Id, StandardId};
+
+/// Set in `flags` for frames with an extended identifier.
+pub const FLAG_EXTENDED: u8 = 1 << 0;
+/// Set in `flags` for remote frames.
+pub const FLAG_REMOTE: u8 = 1 << 1;
+
+/// Frame with C layout, 16 bytes long.
+///
+/// `flags` is a combination of [`FLAG_EXTENDED`] and [`FLAG_REMOTE`]. Data
+/// bytes beyond the DLC are zero.
+#[repr(C)]
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FfiFrame {
+    pub id: u32,
+    pub dlc: u8,
+    pub flags: u8,
+    pub _pad: [u8; 2],
+    pub data: [u8; 8],
+}
+
+/// Frame with packed C layout and no padding, 14 bytes long.
+///
+/// Fields are the same as [`FfiFrame`]. `id` may be unaligned, so copy it out
+/// rather than taking a reference.
+#[repr(C, packed)]
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub struct FfiFramePacked {
+    pub id: u32,
+    pub dlc: u8,
+    pub flags: u8,
+    pub data: [u8; 8],
+}
+
+// Derived implementations would take references to unaligned fields.
+#[cfg(feature = "defmt")]
+impl defmt::Format for FfiFramePacked {
+    fn format(&self, f: defmt::Formatter) {
+        let Self {
+            id,
+            dlc,
+            flags,
+            data,
+        } = *self;
+
+        defmt::write!(
+            f,
+            "FfiFramePacked {{ id: {=u32:#x}, dlc: {=u8}, flags: {=u8:#x}, data: {=[u8]:#x} }}",
+            id,
+            dlc,
+            flags,
+            data,
+        )
+    }
+}
+
+/// Split a frame into its raw identifier, DLC, flags and zero padded data.
+fn to_parts(frame: &Frame) -> (u32, u8, u8, [u8; 8]) {
+    let mut flags = 0;
+    if frame.is_extended() {
+        flags |= FLAG_EXTENDED;
+    }
+    if frame.is_remote_frame() {
+        flags |= FLAG_REMOTE;
+    }
+
+    let mut data = [0; 8];
+    if frame.is_data_frame() {
+        data[..frame.dlc()].copy_from_slice(frame.data());
+    }
+
+    (frame.id_raw(), frame.dlc() as u8, flags, data)
+}
+
+/// Build a frame from raw parts, or `None` if the identifier is out of range
+/// or the DLC is above 8.
+fn from_parts(id: u32, dlc: u8, flags: u8, data: [u8; 8]) -> Option<Frame> {
+    let id = if flags & FLAG_EXTENDED != 0 {
+        Id::Extended(ExtendedId::new(id)?)
+    } else {
+        Id::Standard(StandardId::new(u16::try_from(id).ok()?)?)
+    };
+
+    if flags & FLAG_REMOTE != 0 {
+        Frame::new_rtr(id, dlc as usize)
+    } else {
+        Frame::new(id, data.get(..dlc as usize)?)
+    }
+}
+
+impl From<Frame> for FfiFrame {
+    fn from(frame: Frame) -> Self {
+        let (id, dlc, flags, data) = to_parts(&frame);
+        Self {
+            id,
+            dlc,
+            flags,
+            _pad: [0; 2],
+            data,
+        }
+    }
+}
+
+impl TryFrom<FfiFrame> for Frame {
+    type Error = ();
+
+    fn try_from(frame: FfiFrame) -> Result<Self, Self::Error> {
+        from_parts(frame.id, frame.dlc, frame.flags, frame.data).ok_or(())
+    }
+}
+
+impl From<Frame> for FfiFramePacked {
+    fn from(frame: Frame) -> Self {
+        let (id, dlc, flags, data) = to_parts(&frame);
+        Self {
+            id,
+            dlc,
+            flags,
+            data,
+        }
+    }
+}
+
+impl TryFrom<FfiFramePacked> for Frame {
+    type Error = ();
+
+    fn try_from(frame: FfiFramePacked) -> Result<Self, Self::Error> {
+        from_parts(frame.id, frame.dlc, frame.flags, frame.data).ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout() {
+        assert_eq!(core::mem::size_of::<FfiFrame>(), 16);
+        assert_eq!(core::mem::align_of::<FfiFrame>(), 4);
+        assert_eq!(core::mem::offset_of!(FfiFrame, data), 8);
+        assert_eq!(core::mem::size_of::<FfiFramePacked>(), 14);
+        assert_eq!(core::mem::align_of::<FfiFramePacked>(), 1);
+    }
+
+    #[test]
+    fn round_trip() {
+        let frames = [
+            Frame::new(StandardId::new(0x123).unwrap(), &[0xAA, 0x55]).unwrap(),
+            Frame::new(ExtendedId::MAX, &[1, 2, 3, 4, 5, 6, 7, 8]).unwrap(),
+            Frame::new_remote(ExtendedId::new(0x12ABCDEF).unwrap(), 4).unwrap(),
+        ];
+
+        for frame in frames {
+            assert_eq!(Frame::try_from(FfiFrame::from(frame)), Ok(frame));
+            assert_eq!(Frame::try_from(FfiFramePacked::from(frame)), Ok(frame));
+        }
+
+        let frame = Frame::new_with_dlc(StandardId::new(0x7FF).unwrap(), &[9; 8], 1).unwrap();
+        assert_eq!(
+            FfiFrame::from(frame),
+            FfiFrame {
+                id: 0x7FF,
+                dlc: 1,
+                flags: 0,
+                _pad: [0; 2],
+                data: [9, 0, 0, 0, 0, 0, 0, 0],
+            }
+        );
+
+        let frame = Frame::new_remote(StandardId::new(0x123).unwrap(), 2).unwrap();
+        assert_eq!(FfiFramePacked::from(frame).flags, FLAG_REMOTE);
+    }
+
+    #[test]
+    fn invalid() {
+        let frame = FfiFrame {
+            id: 0x800,
+            ..FfiFrame::default()
+        };
+        assert_eq!(Frame::try_from(frame), Err(()));
+
+        let frame = FfiFrame {
+            id: 0x2000_0000,
+            flags: FLAG_EXTENDED,
+            ..FfiFrame::default()
+        };
+        assert_eq!(Frame::try_from(frame), Err(()));
+
+        let frame = FfiFramePacked {
+            dlc: 9,
+            ..FfiFramePacked::default()
+        };
+        assert_eq!(Frame::try_from(frame), Err(()));
+    }
+}
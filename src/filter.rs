@@ -0,0 +1,214 @@
+//! Software CAN identifier filters.
+
+use crate::Frame;
+use embedded_can::{Frame as _, Id};
+
+/// Identifier acceptance filter.
+pub trait IdFilter {
+    /// Check if a frame with identifier `id` is accepted.
+    fn matches(&self, id: Id) -> bool;
+}
+
+/// SJA1000 dual filter mode.
+///
+/// Each filter section is a 16-bit `(code, mask)` pair. A mask bit of `1`
+/// means the corresponding bit is "don't care". A frame is accepted when
+/// either section matches.
+///
+/// For standard frames each section word holds:
+///
+/// ```text
+/// bit  15 ........ 5 | 4   | 3 .. 0
+///      ID.10 .. ID.0 | RTR | data
+/// ```
+///
+/// For extended frames each section word holds ID.28 to ID.13.
+///
+/// Only the identifier is checked, so the RTR bit and the data bits that
+/// filter A also covers in hardware are ignored here.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DualFilter {
+    pub filter_a: (u16, u16),
+    pub filter_b: (u16, u16),
+}
+
+impl DualFilter {
+    pub fn new(filter_a: (u16, u16), filter_b: (u16, u16)) -> Self {
+        Self { filter_a, filter_b }
+    }
+}
+
+impl IdFilter for DualFilter {
+    fn matches(&self, id: Id) -> bool {
+        let (word, ignored) = match id {
+            Id::Standard(id) => (id.as_raw() << 5, 0x001F),
+            Id::Extended(id) => ((id.as_raw() >> 13) as u16, 0x0000),
+        };
+
+        [self.filter_a, self.filter_b]
+            .iter()
+            .any(|(code, mask)| (word ^ code) & !(mask | ignored) == 0)
+    }
+}
+
+/// Single acceptance code and mask over the whole identifier, as applied by
+/// adapters after [`crate::SetAcceptanceCode`] and [`crate::SetAcceptanceMask`].
+///
+/// A mask bit of `1` means the corresponding bit is "don't care", so an
+/// identifier is accepted when `(id & !mask) == (code & !mask)`.
+///
+/// Identifiers use the 29-bit extended layout. Standard identifiers are
+/// shifted up into bits 28 to 18, where ID.10 lines up with ID.28:
+///
+/// ```text
+/// bit  28 ......... 18 | 17 ......... 0
+///      standard ID     | extended only
+/// ```
+///
+/// Bits 31 to 29 of `code` and `mask` are ignored.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FrameFilter {
+    pub code: u32,
+    pub mask: u32,
+}
+
+impl FrameFilter {
+    pub fn new(code: u32, mask: u32) -> Self {
+        Self { code, mask }
+    }
+
+    /// Filter accepting every identifier.
+    pub fn accept_all() -> Self {
+        Self::new(0, 0xFFFF_FFFF)
+    }
+
+    /// Filter accepting exactly the identifiers `low..=high`, in the 29-bit
+    /// layout.
+    ///
+    /// Returns `None` unless the range is a block whose length is a power of
+    /// two and `low` is a multiple of that length, such as `0x100..=0x1FF`.
+    pub fn from_id_range(low: u32, high: u32) -> Option<Self> {
+        if low > high || high > 0x1FFF_FFFF {
+            return None;
+        }
+
+        let size = high - low + 1;
+        if !size.is_power_of_two() || !low.is_multiple_of(size) {
+            return None;
+        }
+
+        Some(Self::new(low, size - 1))
+    }
+
+    /// Check if `frame` is accepted.
+    ///
+    /// Use [`IdFilter::matches`] to check an identifier on its own.
+    pub fn matches(&self, frame: &Frame) -> bool {
+        IdFilter::matches(self, frame.id())
+    }
+}
+
+impl IdFilter for FrameFilter {
+    fn matches(&self, id: Id) -> bool {
+        let id = match id {
+            Id::Standard(id) => (id.as_raw() as u32) << 18,
+            Id::Extended(id) => id.as_raw(),
+        };
+
+        (id ^ self.code) & !self.mask & 0x1FFF_FFFF == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_can::{ExtendedId, StandardId};
+
+    fn standard(id: u16) -> Id {
+        Id::Standard(StandardId::new(id).unwrap())
+    }
+
+    fn extended(id: u32) -> Id {
+        Id::Extended(ExtendedId::new(id).unwrap())
+    }
+
+    #[test]
+    fn dual_filter_standard() {
+        // Filter A accepts exactly 0x123, filter B accepts 0x200 to 0x20F.
+        let filter = DualFilter::new((0x123 << 5, 0x0000), (0x200 << 5, 0x000F << 5));
+
+        assert!(filter.matches(standard(0x123)));
+        assert!(filter.matches(standard(0x200)));
+        assert!(filter.matches(standard(0x20F)));
+        assert!(!filter.matches(standard(0x124)));
+        assert!(!filter.matches(standard(0x210)));
+    }
+
+    #[test]
+    fn dual_filter_extended() {
+        let filter = DualFilter::new((0x1234, 0x0000), (0xFFFF, 0x0000));
+
+        assert!(filter.matches(extended(0x1234 << 13)));
+        assert!(filter.matches(extended(0x1234 << 13 | 0x1FFF)));
+        assert!(filter.matches(extended(0x1FFFFFFF)));
+        assert!(!filter.matches(extended(0x1235 << 13)));
+    }
+
+    #[test]
+    fn dual_filter_accept_all() {
+        let filter = DualFilter::new((0x0000, 0xFFFF), (0x0000, 0x0000));
+
+        assert!(filter.matches(standard(0x7FF)));
+        assert!(filter.matches(extended(0x1FFFFFFF)));
+    }
+
+    #[test]
+    fn frame_filter() {
+        let filter = FrameFilter::new(0x123 << 18, 0x3FFFF);
+        assert!(IdFilter::matches(&filter, standard(0x123)));
+        assert!(!IdFilter::matches(&filter, standard(0x124)));
+        assert!(IdFilter::matches(&filter, extended(0x123 << 18 | 0x3FFFF)));
+
+        let frame = Frame::new(StandardId::new(0x123).unwrap(), &[]).unwrap();
+        assert!(filter.matches(&frame));
+
+        let filter = FrameFilter::new(0x12ABCDEF, 0);
+        assert!(IdFilter::matches(&filter, extended(0x12ABCDEF)));
+        assert!(!IdFilter::matches(&filter, extended(0x12ABCDEE)));
+
+        let filter = FrameFilter::accept_all();
+        assert!(IdFilter::matches(&filter, standard(0x7FF)));
+        assert!(IdFilter::matches(&filter, extended(0x1FFFFFFF)));
+    }
+
+    #[test]
+    fn frame_filter_from_id_range() {
+        let filter = FrameFilter::from_id_range(0x100, 0x1FF).unwrap();
+        assert_eq!(filter, FrameFilter::new(0x100, 0xFF));
+        assert!(IdFilter::matches(&filter, extended(0x100)));
+        assert!(IdFilter::matches(&filter, extended(0x1FF)));
+        assert!(!IdFilter::matches(&filter, extended(0xFF)));
+        assert!(!IdFilter::matches(&filter, extended(0x200)));
+
+        assert_eq!(
+            FrameFilter::from_id_range(0x42, 0x42),
+            Some(FrameFilter::new(0x42, 0))
+        );
+        assert_eq!(
+            FrameFilter::from_id_range(0, 0x1FFFFFFF),
+            Some(FrameFilter::new(0, 0x1FFFFFFF))
+        );
+
+        // Standard identifiers 0x7E0 to 0x7EF.
+        let filter = FrameFilter::from_id_range(0x7E0 << 18, (0x7EF << 18) | 0x3FFFF).unwrap();
+        assert!(IdFilter::matches(&filter, standard(0x7E8)));
+        assert!(!IdFilter::matches(&filter, standard(0x7F0)));
+
+        assert_eq!(FrameFilter::from_id_range(0x100, 0x180), None);
+        assert_eq!(FrameFilter::from_id_range(0x80, 0x17F), None);
+        assert_eq!(FrameFilter::from_id_range(0x200, 0x100), None);
+        assert_eq!(FrameFilter::from_id_range(0, 0x3FFFFFFF), None);
+    }
+}
@@ -1,7 +1,11 @@
-use embedded_can::Id;
+use embedded_can::{ExtendedId, Id, StandardId};
 
 /// Serial CAN frame.
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+///
+/// Equality and hashing only cover the first DLC data bytes, so padding left
+/// by [`Frame::new_with_dlc`] does not affect comparisons. Use
+/// [`Frame::is_identical_to`] to compare padding too.
+#[derive(Debug, Copy, Clone)]
 pub struct Frame {
     id: Id,
     remote: bool,
@@ -9,6 +13,445 @@ pub struct Frame {
     data: [u8; 8],
 }
 
+impl PartialEq for Frame {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+            && self.remote == other.remote
+            && self.dlc == other.dlc
+            && self.data[..self.dlc as usize] == other.data[..other.dlc as usize]
+    }
+}
+
+impl Eq for Frame {}
+
+impl core::hash::Hash for Frame {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.remote.hash(state);
+        self.dlc.hash(state);
+        self.data[..self.dlc as usize].hash(state);
+    }
+}
+
+/// Only the first DLC data bytes are printed, not the padding.
+#[cfg(feature = "defmt")]
+impl defmt::Format for Frame {
+    fn format(&self, f: defmt::Formatter) {
+        let (id, extended) = match self.id {
+            Id::Standard(id) => (id.as_raw() as u32, false),
+            Id::Extended(id) => (id.as_raw(), true),
+        };
+
+        defmt::write!(
+            f,
+            "Frame {{ id: {=u32:#x}, extended: {=bool}, remote: {=bool}, dlc: {=u8}, data: {=[u8]:#x} }}",
+            id,
+            extended,
+            self.remote,
+            self.dlc,
+            <Self as embedded_can::Frame>::data(self),
+        )
+    }
+}
+
+/// Serialized as the raw identifier, whether it is extended, whether it is a
+/// remote frame, the DLC and the data bytes. Remote frames have no data bytes.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Frame {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let data: &[u8] = if self.remote {
+            &[]
+        } else {
+            &self.data[..self.dlc as usize]
+        };
+
+        let mut state = serializer.serialize_struct("Frame", 5)?;
+        state.serialize_field("id", &self.id_raw())?;
+        state.serialize_field("extended", &matches!(self.id, Id::Extended(_)))?;
+        state.serialize_field("remote", &self.remote)?;
+        state.serialize_field("dlc", &self.dlc)?;
+        state.serialize_field("data", data)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Frame {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Frame")]
+        struct Fields {
+            id: u32,
+            extended: bool,
+            remote: bool,
+            dlc: u8,
+            data: FrameData,
+        }
+
+        let fields = Fields::deserialize(deserializer)?;
+
+        let id = if fields.extended {
+            ExtendedId::new(fields.id).map(Id::Extended)
+        } else {
+            u16::try_from(fields.id)
+                .ok()
+                .and_then(StandardId::new)
+                .map(Id::Standard)
+        }
+        .ok_or_else(|| D::Error::custom("identifier out of range"))?;
+
+        let data = &fields.data.bytes[..fields.data.len];
+        let frame = if fields.remote {
+            if !data.is_empty() {
+                return Err(D::Error::custom("remote frame carries data"));
+            }
+            Frame::new_rtr(id, fields.dlc as usize)
+                .ok_or_else(|| D::Error::custom("dlc greater than 8"))?
+        } else {
+            if fields.dlc as usize != data.len() {
+                return Err(D::Error::custom("dlc does not match data length"));
+            }
+            <Self as embedded_can::Frame>::new(id, data)
+                .ok_or_else(|| D::Error::custom("more than 8 bytes of data"))?
+        };
+
+        Ok(frame)
+    }
+}
+
+/// Up to 8 data bytes deserialized from a sequence.
+#[cfg(feature = "serde")]
+struct FrameData {
+    bytes: [u8; 8],
+    len: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for FrameData {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = FrameData;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "at most 8 data bytes")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut data = FrameData {
+                    bytes: [0; 8],
+                    len: 0,
+                };
+
+                while let Some(byte) = seq.next_element()? {
+                    if data.len == 8 {
+                        return Err(serde::de::Error::invalid_length(9, &self));
+                    }
+                    data.bytes[data.len] = byte;
+                    data.len += 1;
+                }
+
+                Ok(data)
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                if v.len() > 8 {
+                    return Err(E::invalid_length(v.len(), &self));
+                }
+
+                let mut bytes = [0; 8];
+                bytes[..v.len()].copy_from_slice(v);
+
+                Ok(FrameData {
+                    bytes,
+                    len: v.len(),
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+/// CAN identifier types.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum IdType {
+    /// 11-bit standard identifier.
+    Standard11,
+    /// 29-bit extended identifier.
+    Extended29,
+}
+
+impl core::fmt::Display for IdType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            IdType::Standard11 => write!(f, "11-bit"),
+            IdType::Extended29 => write!(f, "29-bit"),
+        }
+    }
+}
+
+impl Frame {
+    /// Create a new data frame from a full 8 byte data word and a separate
+    /// DLC, as provided by many CAN controllers.
+    ///
+    /// Bytes beyond the DLC are kept as padding. Returns `None` if `dlc` is
+    /// greater than 8.
+    pub fn new_with_dlc(id: impl Into<Id>, data: &[u8; 8], dlc: u8) -> Option<Self> {
+        if dlc > 8 {
+            return None;
+        }
+
+        Some(Self {
+            id: id.into(),
+            remote: false,
+            dlc,
+            data: *data,
+        })
+    }
+
+    /// Create a zero length data frame with identifier `id`.
+    pub(crate) const fn new_empty(id: Id) -> Self {
+        Self {
+            id,
+            remote: false,
+            dlc: 0,
+            data: [0; 8],
+        }
+    }
+
+    /// Create a new remote transmission request (RTR) frame.
+    ///
+    /// Same as [`embedded_can::Frame::new_remote`].
+    #[inline]
+    pub fn new_rtr(id: impl Into<Id>, dlc: usize) -> Option<Self> {
+        <Self as embedded_can::Frame>::new_remote(id, dlc)
+    }
+
+    /// Create the remote transmission request (RTR) for this data frame, with
+    /// the same identifier and DLC.
+    ///
+    /// Returns `None` if this frame is already a remote frame.
+    pub fn new_remote_from_data_frame(&self) -> Option<Self> {
+        if self.remote {
+            return None;
+        }
+
+        Self::new_rtr(self.id, self.dlc as usize)
+    }
+
+    /// Create a data frame from a hex encoded identifier and data.
+    ///
+    /// `id` is 3 hex digits for a standard identifier or 8 for an extended
+    /// one, and `data_hex` is up to 8 bytes as pairs of hex digits. Returns
+    /// `None` if either does not parse.
+    pub fn new_from_str_radix(id: &str, data_hex: &str) -> Option<Frame> {
+        crate::Transmit::try_from_hex_parts(id, data_hex)
+            .ok()
+            .map(crate::Transmit::into_frame)
+    }
+
+    /// Check if this is a remote transmission request (RTR) frame.
+    ///
+    /// Same as [`embedded_can::Frame::is_remote_frame`].
+    #[inline]
+    pub fn is_rtr(&self) -> bool {
+        <Self as embedded_can::Frame>::is_remote_frame(self)
+    }
+
+    /// Check if two frames are identical, including all 8 data bytes.
+    ///
+    /// Unlike `==` and [`data`](embedded_can::Frame::data), which only cover
+    /// the first DLC bytes, this also compares the padding bytes. This matters
+    /// when the full 8 byte word is meaningful, for example when mirroring
+    /// EEPROM contents.
+    pub fn is_identical_to(&self, other: &Frame) -> bool {
+        self.id == other.id
+            && self.remote == other.remote
+            && self.dlc == other.dlc
+            && self.data == other.data
+    }
+
+    /// The numeric value of this frame's identifier.
+    pub fn id_raw(&self) -> u32 {
+        match self.id {
+            Id::Standard(id) => id.as_raw() as u32,
+            Id::Extended(id) => id.as_raw(),
+        }
+    }
+
+    /// Check if the numeric identifier is within `lo..=hi`.
+    ///
+    /// Standard and extended identifiers are compared by value alone.
+    pub fn id_is_in_range(&self, lo: u32, hi: u32) -> bool {
+        (lo..=hi).contains(&self.id_raw())
+    }
+
+    /// Check if the numeric identifier equals `target`.
+    pub fn id_is_exactly(&self, target: u32) -> bool {
+        self.id_raw() == target
+    }
+
+    /// Copy of this frame with its identifier replaced by `id`.
+    pub fn with_id(self, id: impl Into<Id>) -> Self {
+        Frame {
+            id: id.into(),
+            ..self
+        }
+    }
+
+    /// Convert to a frame with an extended identifier of the same numeric
+    /// value. Frames that are already extended are returned unchanged.
+    pub fn into_extended(self) -> Frame {
+        let id = match self.id {
+            // Any standard ID value fits in an extended ID.
+            Id::Standard(id) => Id::Extended(ExtendedId::new(id.as_raw() as u32).unwrap()),
+            Id::Extended(_) => self.id,
+        };
+
+        Frame { id, ..self }
+    }
+
+    /// Iterate over the frame data.
+    #[inline]
+    pub fn data_iter(&self) -> core::slice::Iter<'_, u8> {
+        <Self as embedded_can::Frame>::data(self).iter()
+    }
+
+    /// Check if the frame data is exactly `expected`.
+    #[inline]
+    pub fn data_equals(&self, expected: &[u8]) -> bool {
+        <Self as embedded_can::Frame>::data(self) == expected
+    }
+
+    /// Check if the frame data starts with `prefix`.
+    #[inline]
+    pub fn data_starts_with(&self, prefix: &[u8]) -> bool {
+        <Self as embedded_can::Frame>::data(self).starts_with(prefix)
+    }
+
+    /// Check if the frame data ends with `suffix`.
+    #[inline]
+    pub fn data_ends_with(&self, suffix: &[u8]) -> bool {
+        <Self as embedded_can::Frame>::data(self).ends_with(suffix)
+    }
+
+    /// The frame data as text, or `None` if any byte is not ASCII.
+    pub fn data_as_ascii(&self) -> Option<&str> {
+        let data = &self.data[0..self.dlc as usize];
+
+        if !data.is_ascii() {
+            return None;
+        }
+
+        core::str::from_utf8(data).ok()
+    }
+
+    /// Pack the frame into a single integer.
+    ///
+    /// | Bits    | Field         |
+    /// |---------|---------------|
+    /// | 127..97 | Identifier    |
+    /// | 96      | Extended flag |
+    /// | 95      | Remote flag   |
+    /// | 91..88  | DLC           |
+    /// | 63..0   | Data, byte 0 in the most significant byte |
+    ///
+    /// All 8 data bytes are packed, including padding beyond the DLC.
+    pub fn to_u128(&self) -> u128 {
+        let extended = matches!(self.id, Id::Extended(_));
+
+        (self.id_raw() as u128) << 97
+            | (extended as u128) << 96
+            | (self.remote as u128) << 95
+            | (self.dlc as u128) << 88
+            | u64::from_be_bytes(self.data) as u128
+    }
+
+    /// Unpack a frame packed by [`Frame::to_u128`].
+    ///
+    /// Returns `None` if the identifier or DLC is out of range.
+    pub fn from_u128(packed: u128) -> Option<Frame> {
+        let raw = (packed >> 97) as u32;
+        let id = if packed & 1 << 96 != 0 {
+            Id::Extended(ExtendedId::new(raw)?)
+        } else {
+            Id::Standard(StandardId::new(u16::try_from(raw).ok()?)?)
+        };
+
+        let dlc = (packed >> 88) as u8 & 0x0F;
+        if dlc > 8 {
+            return None;
+        }
+
+        Some(Frame {
+            id,
+            remote: packed & 1 << 95 != 0,
+            dlc,
+            data: (packed as u64).to_be_bytes(),
+        })
+    }
+
+    /// Check if the frame has an 11-bit standard identifier.
+    #[inline]
+    pub fn is_11bit(&self) -> bool {
+        !<Self as embedded_can::Frame>::is_extended(self)
+    }
+
+    /// Check if the frame has a 29-bit extended identifier.
+    #[inline]
+    pub fn is_29bit(&self) -> bool {
+        <Self as embedded_can::Frame>::is_extended(self)
+    }
+
+    /// The [`IdType`] of this frame's identifier.
+    pub fn id_type(&self) -> IdType {
+        match self.id {
+            Id::Standard(_) => IdType::Standard11,
+            Id::Extended(_) => IdType::Extended29,
+        }
+    }
+}
+
+/// Iterate over the frame data, same as [`Frame::data_iter`].
+impl<'a> IntoIterator for &'a Frame {
+    type Item = &'a u8;
+    type IntoIter = core::slice::Iter<'a, u8>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data_iter()
+    }
+}
+
+/// Data bytes as space separated, three digit octal numbers.
+///
+/// Remote frames carry no data and format as an empty string.
+impl core::fmt::Octal for Frame {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.remote {
+            return Ok(());
+        }
+
+        for (i, byte) in self.data[..self.dlc as usize].iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{:03o}", byte)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl embedded_can::Frame for Frame {
     fn new(id: impl Into<Id>, data: &[u8]) -> Option<Self> {
         if data.len() > 8 {
@@ -16,7 +459,7 @@ impl embedded_can::Frame for Frame {
         }
 
         let mut data_all = [0; 8];
-        data_all[0..data.len()].copy_from_slice(&data);
+        data_all[0..data.len()].copy_from_slice(data);
 
         Some(Self {
             id: id.into(),
@@ -62,3 +505,22 @@ impl embedded_can::Frame for Frame {
         self.remote
     }
 }
+
+/// Format an [`Id`] as its canonical slcan hex string.
+///
+/// Standard IDs are written as 3 hex digits and extended IDs as 8 hex digits,
+/// matching the encoding used by [`Transmit`](crate::Transmit).
+#[cfg(feature = "heapless")]
+pub fn frame_id_as_string(id: Id) -> heapless::String<12> {
+    use core::fmt::Write;
+
+    let mut string = heapless::String::new();
+
+    // Capacity is large enough for any ID so writing cannot fail.
+    match id {
+        Id::Standard(id) => write!(string, "{:03X}", id.as_raw()).unwrap(),
+        Id::Extended(id) => write!(string, "{:08X}", id.as_raw()).unwrap(),
+    }
+
+    string
+}
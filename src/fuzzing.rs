@@ -0,0 +1,77 @@
+//! [`arbitrary`] implementations for fuzzing and property testing.
+//!
+//! These are written by hand rather than derived so that every generated
+//! value is valid: frames carry a DLC of 0 to 8 and a standard or extended
+//! identifier within range.
+
+use crate::{Bitrate, Frame, Transmit};
+use arbitrary::{Arbitrary, Result, Unstructured};
+use embedded_can::{ExtendedId, Frame as _, Id, StandardId};
+
+impl<'a> Arbitrary<'a> for Bitrate {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(*u.choose(&Bitrate::ALL)?)
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (1, Some(4))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Frame {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let id: Id = if u.arbitrary()? {
+            ExtendedId::new(u.int_in_range(0..=ExtendedId::MAX.as_raw())?)
+                .unwrap()
+                .into()
+        } else {
+            StandardId::new(u.int_in_range(0..=StandardId::MAX.as_raw())?)
+                .unwrap()
+                .into()
+        };
+
+        let remote: bool = u.arbitrary()?;
+        let dlc = u.int_in_range(0..=8)?;
+
+        let frame = if remote {
+            Frame::new_remote(id, dlc)
+        } else {
+            let data: [u8; 8] = u.arbitrary()?;
+            Frame::new(id, &data[..dlc])
+        };
+
+        Ok(frame.unwrap())
+    }
+}
+
+impl<'a> Arbitrary<'a> for Transmit {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Transmit::new(&Frame::arbitrary(u)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arbitrary_frames_are_valid() {
+        let bytes: Vec<u8> = (0..=255).cycle().take(4096).collect();
+        let mut u = Unstructured::new(&bytes);
+
+        while !u.is_empty() {
+            let Ok(transmit) = Transmit::arbitrary(&mut u) else {
+                break;
+            };
+
+            let encoded = format!("{}", transmit);
+            assert_eq!(Transmit::try_parse(&encoded), Ok(("", transmit)));
+        }
+    }
+
+    #[test]
+    fn arbitrary_bitrate() {
+        let mut u = Unstructured::new(&[8]);
+        assert_eq!(Bitrate::arbitrary(&mut u), Ok(Bitrate::Rate1000kbit));
+    }
+}
@@ -0,0 +1,84 @@
+//! Hex dumps of raw slcan byte streams.
+
+/// Formats bytes as a hex dump with an ASCII column, like Wireshark.
+///
+/// Each line holds 16 bytes, prefixed by the offset of the first byte.
+/// Bytes outside printable ASCII, such as the `\r` terminator, show as `.` in
+/// the ASCII column.
+///
+/// ```text
+/// 0000  74 31 32 33 31 41 41 0d  4f 0d                     t1231AA. O.
+/// ```
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SlcanHexDump<'a>(pub &'a [u8]);
+
+impl core::fmt::Display for SlcanHexDump<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (line, chunk) in self.0.chunks(16).enumerate() {
+            if line > 0 {
+                writeln!(f)?;
+            }
+
+            write!(f, "{:04x} ", line * 16)?;
+
+            for i in 0..16 {
+                if i == 8 {
+                    write!(f, " ")?;
+                }
+                match chunk.get(i) {
+                    Some(byte) => write!(f, " {:02x}", byte)?,
+                    None => write!(f, "   ")?,
+                }
+            }
+
+            write!(f, "   ")?;
+
+            for (i, byte) in chunk.iter().enumerate() {
+                if i == 8 {
+                    write!(f, " ")?;
+                }
+                let c = if byte.is_ascii_graphic() || *byte == b' ' {
+                    *byte as char
+                } else {
+                    '.'
+                };
+                write!(f, "{}", c)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_line() {
+        assert_eq!(
+            format!("{}", SlcanHexDump(b"t1231AA\rO\r")),
+            "0000  74 31 32 33 31 41 41 0d  4f 0d                     t1231AA. O."
+        );
+    }
+
+    #[test]
+    fn multiple_lines() {
+        let dump = format!("{}", SlcanHexDump(b"S6\rO\rt1232AA55\rT12ABCDEF0\r"));
+        let lines: Vec<&str> = dump.lines().collect();
+
+        assert_eq!(
+            lines,
+            [
+                "0000  53 36 0d 4f 0d 74 31 32  33 32 41 41 35 35 0d 54   S6.O.t12 32AA55.T",
+                "0010  31 32 41 42 43 44 45 46  30 0d                     12ABCDEF 0.",
+            ]
+        );
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(format!("{}", SlcanHexDump(&[])), "");
+    }
+}
@@ -0,0 +1,3 @@
+//! Conversions for specific CAN peripherals.
+
+pub mod stm32;
@@ -0,0 +1,59 @@
+//! STM32 bxCAN peripheral.
+
+use crate::Frame;
+use embedded_can::{ExtendedId, Id, StandardId};
+
+impl Frame {
+    /// Create a data frame from a bxCAN receive mailbox.
+    ///
+    /// `drl` and `drh` are the mailbox data registers. DRL holds bytes 0 to 3
+    /// and DRH holds bytes 4 to 7, with the lowest numbered byte in bits 7:0.
+    /// Bytes beyond `dlc` are kept as padding.
+    ///
+    /// Returns `None` if `id_raw` is out of range for the identifier type or
+    /// `dlc` is greater than 8.
+    pub fn new_from_words(
+        id_raw: u32,
+        extended: bool,
+        dlc: u8,
+        drl: u32,
+        drh: u32,
+    ) -> Option<Frame> {
+        let id = if extended {
+            Id::Extended(ExtendedId::new(id_raw)?)
+        } else {
+            Id::Standard(StandardId::new(u16::try_from(id_raw).ok()?)?)
+        };
+
+        let mut data = [0; 8];
+        data[..4].copy_from_slice(&drl.to_le_bytes());
+        data[4..].copy_from_slice(&drh.to_le_bytes());
+
+        Frame::new_with_dlc(id, &data, dlc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_can::Frame as _;
+
+    #[test]
+    fn new_from_words() {
+        let frame = Frame::new_from_words(0x123, false, 8, 0x4433_2211, 0x8877_6655).unwrap();
+        assert_eq!(frame.id(), Id::Standard(StandardId::new(0x123).unwrap()));
+        assert_eq!(
+            frame.data(),
+            &[0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88]
+        );
+
+        let frame = Frame::new_from_words(0x1234_5678, true, 3, 0x0033_2211, 0).unwrap();
+        assert!(frame.is_extended());
+        assert_eq!(frame.data(), &[0x11, 0x22, 0x33]);
+
+        assert!(Frame::new_from_words(0x800, false, 0, 0, 0).is_none());
+        assert!(Frame::new_from_words(0x1_0123, false, 0, 0, 0).is_none());
+        assert!(Frame::new_from_words(0x2000_0000, true, 0, 0, 0).is_none());
+        assert!(Frame::new_from_words(0x123, false, 9, 0, 0).is_none());
+    }
+}
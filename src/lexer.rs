@@ -0,0 +1,234 @@
+//! Tokenising slcan byte streams.
+
+/// Lexical tokens of the slcan protocol.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Token {
+    /// Command letter, such as `S` or `t`.
+    CommandStart(u8),
+    /// Byte encoded as two hex digits.
+    HexByte(u8),
+    /// Single hex digit field, such as an identifier nibble, DLC or bitrate
+    /// index.
+    Digit(u8),
+    /// Command terminator (`\r`).
+    Terminator,
+}
+
+/// Unexpected byte in the input.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LexError {
+    /// Offset of the byte in the input.
+    pub position: usize,
+    pub byte: u8,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+enum State {
+    Start,
+    Digits(usize),
+    Bytes,
+    Failed,
+}
+
+/// Number of single digit fields that follow a command letter. Anything
+/// after those fields is lexed as hex bytes.
+fn digit_fields(command: u8) -> usize {
+    match command {
+        b'S' | b'U' | b'Z' => 1,
+        b't' | b'r' => 4,
+        b'T' | b'R' => 9,
+        _ => 0,
+    }
+}
+
+fn hex_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        _ => None,
+    }
+}
+
+/// Iterator producing [`Token`]s from slcan bytes.
+///
+/// Iteration stops after the first [`LexError`].
+///
+/// [`Transmit`](crate::Transmit) commands, and the frames in
+/// [`Command`](crate::Command)s, are parsed from these tokens.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SlcanLexer<'a> {
+    input: &'a [u8],
+    position: usize,
+    state: State,
+}
+
+impl<'a> SlcanLexer<'a> {
+    pub fn new(input: &'a [u8]) -> Self {
+        Self {
+            input,
+            position: 0,
+            state: State::Start,
+        }
+    }
+
+    /// Number of bytes consumed so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    fn error(&mut self, position: usize) -> Option<Result<Token, LexError>> {
+        self.state = State::Failed;
+        Some(Err(LexError {
+            position,
+            byte: self.input[position],
+        }))
+    }
+}
+
+impl Iterator for SlcanLexer<'_> {
+    type Item = Result<Token, LexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.state == State::Failed {
+            return None;
+        }
+
+        let position = self.position;
+        let byte = *self.input.get(position)?;
+
+        if byte == b'\r' {
+            self.position += 1;
+            self.state = State::Start;
+            return Some(Ok(Token::Terminator));
+        }
+
+        match self.state {
+            State::Start => {
+                if !byte.is_ascii_alphabetic() {
+                    return self.error(position);
+                }
+
+                self.position += 1;
+                self.state = match digit_fields(byte) {
+                    0 => State::Bytes,
+                    n => State::Digits(n),
+                };
+                Some(Ok(Token::CommandStart(byte)))
+            }
+            State::Digits(remaining) => {
+                let Some(value) = hex_value(byte) else {
+                    return self.error(position);
+                };
+
+                self.position += 1;
+                self.state = match remaining {
+                    1 => State::Bytes,
+                    n => State::Digits(n - 1),
+                };
+                Some(Ok(Token::Digit(value)))
+            }
+            State::Bytes => {
+                let Some(high) = hex_value(byte) else {
+                    return self.error(position);
+                };
+                let Some(low) = self.input.get(position + 1).copied().and_then(hex_value) else {
+                    return match self.input.get(position + 1) {
+                        Some(_) => self.error(position + 1),
+                        None => None,
+                    };
+                };
+
+                self.position += 2;
+                Some(Ok(Token::HexByte(high << 4 | low)))
+            }
+            State::Failed => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lex(input: &[u8]) -> Vec<Result<Token, LexError>> {
+        SlcanLexer::new(input).collect()
+    }
+
+    #[test]
+    fn lex_setup_open() {
+        assert_eq!(
+            lex(b"S6\rO\r"),
+            [
+                Ok(Token::CommandStart(b'S')),
+                Ok(Token::Digit(6)),
+                Ok(Token::Terminator),
+                Ok(Token::CommandStart(b'O')),
+                Ok(Token::Terminator),
+            ]
+        );
+    }
+
+    #[test]
+    fn lex_transmit() {
+        assert_eq!(
+            lex(b"t1232aA55\r"),
+            [
+                Ok(Token::CommandStart(b't')),
+                Ok(Token::Digit(1)),
+                Ok(Token::Digit(2)),
+                Ok(Token::Digit(3)),
+                Ok(Token::Digit(2)),
+                Ok(Token::HexByte(0xAA)),
+                Ok(Token::HexByte(0x55)),
+                Ok(Token::Terminator),
+            ]
+        );
+
+        let tokens = lex(b"T12ABCDEF0\r");
+        assert_eq!(tokens.len(), 11);
+        assert_eq!(tokens[9], Ok(Token::Digit(0)));
+    }
+
+    #[test]
+    fn lex_ack() {
+        assert_eq!(lex(b"\r"), [Ok(Token::Terminator)]);
+    }
+
+    #[test]
+    fn lex_errors() {
+        assert_eq!(
+            lex(b"1\r"),
+            [Err(LexError {
+                position: 0,
+                byte: b'1'
+            })]
+        );
+        assert_eq!(
+            lex(b"t12G")[3],
+            Err(LexError {
+                position: 3,
+                byte: b'G'
+            })
+        );
+        assert_eq!(
+            lex(b"t12310\r")[5],
+            Err(LexError {
+                position: 6,
+                byte: b'\r'
+            })
+        );
+    }
+
+    #[test]
+    fn lex_partial() {
+        // A trailing half byte waits for more input.
+        let mut lexer = SlcanLexer::new(b"t12310");
+        assert_eq!(lexer.by_ref().count(), 5);
+        assert_eq!(lexer.position(), 5);
+    }
+}
@@ -2,10 +2,54 @@
 
 #![cfg_attr(not(test), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "heapless")]
+mod batch;
+#[cfg(feature = "alloc")]
+pub mod candump;
+pub mod checksum;
+pub mod device;
+pub mod diagnostics;
+mod encode;
+mod error;
+#[cfg(feature = "can-fd")]
+mod fd;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod filter;
 mod frame;
+#[cfg(feature = "fuzzing")]
+mod fuzzing;
+mod hexdump;
+pub mod interop;
+pub mod lexer;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+mod parser;
+mod response;
+mod sequencer;
+pub mod signal;
+#[cfg(feature = "embedded-io")]
+mod sink;
+mod timestamp;
+pub mod timing;
 
+#[cfg(feature = "heapless")]
+pub use batch::CommandBatch;
+use core::fmt::Write as _;
+pub use device::SlcanDevice;
 use embedded_can::{ExtendedId, Frame as _, Id, StandardId};
-pub use frame::Frame;
+pub use encode::{EncodeCommand, EncodingBytes};
+pub use error::{EncodeError, ParseError, SlcanError};
+#[cfg(feature = "can-fd")]
+pub use fd::{CanFdFrame, DlcCode, TransmitFd};
+#[cfg(feature = "heapless")]
+pub use frame::frame_id_as_string;
+pub use frame::{Frame, IdType};
+pub use hexdump::SlcanHexDump;
+use lexer::{LexError, SlcanLexer, Token};
 use nom::{
     branch::alt,
     bytes::complete::{tag, take},
@@ -15,9 +59,31 @@ use nom::{
     sequence::tuple,
     Err, IResult,
 };
+pub use parser::{CommandParser, FeedResult, SlcanParser};
+#[allow(deprecated)]
+pub use response::SlcanResponse;
+pub use response::{
+    parse_slcan_response, GetSerialNumber, GetVersion, ReadStatusFlags, Response,
+    SerialNumberResponse, StatusFlagsResponse, VersionResponse,
+};
+pub use sequencer::{SequencerState, SlcanCommandSequencer};
+#[cfg(feature = "embedded-io")]
+pub use sink::SlcanSink;
+pub use timestamp::{FrameTimestamp, SlcanTimestamp};
+
+/// Maximum encoded length of any command, a timestamped extended data frame
+/// carrying 8 bytes.
+pub const MAX_COMMAND_LEN: usize = 31;
+
+/// Maximum encoded length of a [`Transmit`] command, an extended data frame
+/// carrying 8 bytes: `T`, 8 identifier digits, the DLC, 16 data digits and
+/// `\r`.
+pub const MAX_FRAME_ENCODED_LEN: usize = 27;
 
 /// Bitrate options.
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum Bitrate {
     Rate10kbit = 0,
@@ -31,8 +97,151 @@ pub enum Bitrate {
     Rate1000kbit = 8,
 }
 
+impl Bitrate {
+    /// All bitrates, in ascending order.
+    pub const ALL: [Bitrate; 9] = [
+        Bitrate::Rate10kbit,
+        Bitrate::Rate20kbit,
+        Bitrate::Rate50kbit,
+        Bitrate::Rate100kbit,
+        Bitrate::Rate125kbit,
+        Bitrate::Rate250kbit,
+        Bitrate::Rate500kbit,
+        Bitrate::Rate800kbit,
+        Bitrate::Rate1000kbit,
+    ];
+
+    /// All bitrates in bits per second, in ascending order.
+    pub const ALL_BPS: [u32; 9] = [
+        10_000, 20_000, 50_000, 100_000, 125_000, 250_000, 500_000, 800_000, 1_000_000,
+    ];
+
+    /// Check if this is one of the bitrates commonly used on automotive
+    /// networks built to ISO 11898: 125, 250, 500 and 1000 kbit/s.
+    pub const fn is_automotive_standard(&self) -> bool {
+        matches!(
+            self,
+            Bitrate::Rate125kbit
+                | Bitrate::Rate250kbit
+                | Bitrate::Rate500kbit
+                | Bitrate::Rate1000kbit
+        )
+    }
+
+    /// Human readable name, such as `"500 kbit/s"`.
+    pub const fn description(&self) -> &'static str {
+        match self {
+            Bitrate::Rate10kbit => "10 kbit/s",
+            Bitrate::Rate20kbit => "20 kbit/s",
+            Bitrate::Rate50kbit => "50 kbit/s",
+            Bitrate::Rate100kbit => "100 kbit/s",
+            Bitrate::Rate125kbit => "125 kbit/s",
+            Bitrate::Rate250kbit => "250 kbit/s",
+            Bitrate::Rate500kbit => "500 kbit/s",
+            Bitrate::Rate800kbit => "800 kbit/s",
+            Bitrate::Rate1000kbit => "1000 kbit/s",
+        }
+    }
+
+    /// The bitrate in bits per second.
+    pub const fn as_bps(&self) -> u32 {
+        match self {
+            Bitrate::Rate10kbit => 10_000,
+            Bitrate::Rate20kbit => 20_000,
+            Bitrate::Rate50kbit => 50_000,
+            Bitrate::Rate100kbit => 100_000,
+            Bitrate::Rate125kbit => 125_000,
+            Bitrate::Rate250kbit => 250_000,
+            Bitrate::Rate500kbit => 500_000,
+            Bitrate::Rate800kbit => 800_000,
+            Bitrate::Rate1000kbit => 1_000_000,
+        }
+    }
+
+    /// Look up the bitrate for `bps` bits per second.
+    ///
+    /// Returns `None` for non-standard bitrates such as 333 kbit/s.
+    pub const fn try_from_bps(bps: u32) -> Option<Self> {
+        match bps {
+            10_000 => Some(Bitrate::Rate10kbit),
+            20_000 => Some(Bitrate::Rate20kbit),
+            50_000 => Some(Bitrate::Rate50kbit),
+            100_000 => Some(Bitrate::Rate100kbit),
+            125_000 => Some(Bitrate::Rate125kbit),
+            250_000 => Some(Bitrate::Rate250kbit),
+            500_000 => Some(Bitrate::Rate500kbit),
+            800_000 => Some(Bitrate::Rate800kbit),
+            1_000_000 => Some(Bitrate::Rate1000kbit),
+            _ => None,
+        }
+    }
+
+    /// Duration of one bit in nanoseconds.
+    pub const fn nominal_bit_time_ns(&self) -> u32 {
+        1_000_000_000 / self.as_bps()
+    }
+
+    /// Length of the intermission between frames in bits.
+    pub const fn intermission_bits() -> u8 {
+        3
+    }
+
+    /// Duration of the intermission between frames in nanoseconds.
+    pub const fn intermission_ns(&self) -> u32 {
+        self.nominal_bit_time_ns() * Self::intermission_bits() as u32
+    }
+
+    /// Bit timing for a CAN peripheral clocked at `clock_hz`.
+    ///
+    /// Timings are precomputed for 8, 16, 24 and 48 MHz clocks. Returns `None`
+    /// for any other clock frequency.
+    pub fn to_can_timing(&self, clock_hz: u32) -> Option<timing::CanTiming> {
+        timing::precomputed(*self, clock_hz)
+    }
+}
+
+/// Convert from the LAWICEL `Sn` command index, not the bitrate value.
+///
+/// `0` is [`Bitrate::Rate10kbit`] and `8` is [`Bitrate::Rate1000kbit`].
+impl TryFrom<u8> for Bitrate {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Bitrate::Rate10kbit),
+            1 => Ok(Bitrate::Rate20kbit),
+            2 => Ok(Bitrate::Rate50kbit),
+            3 => Ok(Bitrate::Rate100kbit),
+            4 => Ok(Bitrate::Rate125kbit),
+            5 => Ok(Bitrate::Rate250kbit),
+            6 => Ok(Bitrate::Rate500kbit),
+            7 => Ok(Bitrate::Rate800kbit),
+            8 => Ok(Bitrate::Rate1000kbit),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Convert to the LAWICEL `Sn` command index, not the bitrate value.
+impl From<Bitrate> for u8 {
+    fn from(bitrate: Bitrate) -> Self {
+        bitrate as u8
+    }
+}
+
+/// Convert from a bitrate in bits per second, such as `500_000`.
+impl TryFrom<u32> for Bitrate {
+    type Error = ();
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        Bitrate::try_from_bps(value).ok_or(())
+    }
+}
+
 /// Setup port command.
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Setup {
     pub bitrate: Bitrate,
 }
@@ -42,6 +251,11 @@ impl Setup {
         Self { bitrate }
     }
 
+    /// Iterate over the encoded bytes of this command.
+    pub fn encoding_bytes(&self) -> EncodingBytes {
+        EncodingBytes::new(self)
+    }
+
     /// Try parsing a [`Setup`] command from a string.
     pub fn try_parse(input: &str) -> IResult<&str, Self> {
         let (input, (_, bitrate, _)) = tuple((tag("S"), digit1, tag("\r")))(input)?;
@@ -61,6 +275,14 @@ impl Setup {
 
         Ok((input, Self { bitrate }))
     }
+
+    /// Try parsing a [`Setup`] command from a byte slice.
+    ///
+    /// Equivalent to [`Setup::try_parse`] without requiring the caller to
+    /// validate the input as UTF-8 first.
+    pub fn try_parse_bytes(input: &[u8]) -> IResult<&[u8], Self> {
+        parse_ascii(input, Self::try_parse)
+    }
 }
 
 impl core::fmt::Display for Setup {
@@ -69,8 +291,46 @@ impl core::fmt::Display for Setup {
     }
 }
 
-/// Open port command.
+/// Setup port with custom bit timing command.
+///
+/// Writes the SJA1000 compatible BTR0 and BTR1 bit timing registers directly,
+/// for bitrates not covered by [`Setup`].
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SetupBtr {
+    pub btr0: u8,
+    pub btr1: u8,
+}
+
+impl SetupBtr {
+    pub fn new(btr0: u8, btr1: u8) -> Self {
+        Self { btr0, btr1 }
+    }
+
+    /// Iterate over the encoded bytes of this command.
+    pub fn encoding_bytes(&self) -> EncodingBytes {
+        EncodingBytes::new(self)
+    }
+
+    /// Try parsing a [`SetupBtr`] command from a string.
+    pub fn try_parse(input: &str) -> IResult<&str, Self> {
+        let (input, (_, btr0, btr1, _)) =
+            tuple((tag("s"), hex_number(2), hex_number(2), tag("\r")))(input)?;
+
+        Ok((input, Self::new(btr0 as u8, btr1 as u8)))
+    }
+}
+
+impl core::fmt::Display for SetupBtr {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "s{:02X}{:02X}\r", self.btr0, self.btr1)
+    }
+}
+
+/// Open port command.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Open {}
 
 impl Open {
@@ -78,12 +338,25 @@ impl Open {
         Self {}
     }
 
+    /// Iterate over the encoded bytes of this command.
+    pub fn encoding_bytes(&self) -> EncodingBytes {
+        EncodingBytes::new(self)
+    }
+
     /// Try parsing an [`Open`] command from a string.
     pub fn try_parse(input: &str) -> IResult<&str, Self> {
         let (input, _) = tag("O\r")(input)?;
 
         Ok((input, Self::new()))
     }
+
+    /// Try parsing an [`Open`] command from a byte slice.
+    ///
+    /// Equivalent to [`Open::try_parse`] without requiring the caller to
+    /// validate the input as UTF-8 first.
+    pub fn try_parse_bytes(input: &[u8]) -> IResult<&[u8], Self> {
+        parse_ascii(input, Self::try_parse)
+    }
 }
 
 impl core::fmt::Display for Open {
@@ -92,8 +365,45 @@ impl core::fmt::Display for Open {
     }
 }
 
+/// Open port in listen-only mode command.
+///
+/// The adapter receives frames but never acknowledges or transmits, so it
+/// stays invisible to the rest of the bus.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OpenListenOnly {}
+
+impl OpenListenOnly {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Iterate over the encoded bytes of this command.
+    pub fn encoding_bytes(&self) -> EncodingBytes {
+        EncodingBytes::new(self)
+    }
+
+    /// Try parsing an [`OpenListenOnly`] command from a string.
+    pub fn try_parse(input: &str) -> IResult<&str, Self> {
+        let (input, _) = tag("L\r")(input)?;
+
+        Ok((input, Self::new()))
+    }
+}
+
+impl core::fmt::Display for OpenListenOnly {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "L\r")
+    }
+}
+
 /// Close port command.
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+///
+/// Closing the port returns the adapter to its unconfigured state. A new
+/// [`Setup`] must be sent before the port is opened again.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Close {}
 
 impl Close {
@@ -101,12 +411,25 @@ impl Close {
         Self {}
     }
 
+    /// Iterate over the encoded bytes of this command.
+    pub fn encoding_bytes(&self) -> EncodingBytes {
+        EncodingBytes::new(self)
+    }
+
     /// Try parsing a [`Close`] command from a string.
     pub fn try_parse(input: &str) -> IResult<&str, Self> {
         let (input, _) = tag("C\r")(input)?;
 
         Ok((input, Self::new()))
     }
+
+    /// Try parsing a [`Close`] command from a byte slice.
+    ///
+    /// Equivalent to [`Close::try_parse`] without requiring the caller to
+    /// validate the input as UTF-8 first.
+    pub fn try_parse_bytes(input: &[u8]) -> IResult<&[u8], Self> {
+        parse_ascii(input, Self::try_parse)
+    }
 }
 
 impl core::fmt::Display for Close {
@@ -115,8 +438,230 @@ impl core::fmt::Display for Close {
     }
 }
 
-/// Transmit frame command.
+/// Timestamp modes.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum TimestampMode {
+    Off = 0,
+    On = 1,
+}
+
+impl TryFrom<u8> for TimestampMode {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(TimestampMode::Off),
+            1 => Ok(TimestampMode::On),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Set timestamp mode command.
+///
+/// While timestamps are on, the adapter appends a 16-bit millisecond
+/// [`SlcanTimestamp`] to every received frame.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SetTimestampMode {
+    pub mode: TimestampMode,
+}
+
+impl SetTimestampMode {
+    pub fn new(mode: TimestampMode) -> Self {
+        Self { mode }
+    }
+
+    /// Iterate over the encoded bytes of this command.
+    pub fn encoding_bytes(&self) -> EncodingBytes {
+        EncodingBytes::new(self)
+    }
+
+    /// Try parsing a [`SetTimestampMode`] command from a string.
+    pub fn try_parse(input: &str) -> IResult<&str, Self> {
+        let (input, (_, mode, _)) = tuple((tag("Z"), one_of("01"), tag("\r")))(input)?;
+
+        let mode = match mode {
+            '1' => TimestampMode::On,
+            _ => TimestampMode::Off,
+        };
+
+        Ok((input, Self { mode }))
+    }
+}
+
+impl core::fmt::Display for SetTimestampMode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Z{}\r", self.mode as u8)
+    }
+}
+
+/// UART baud rates of the adapter's serial port.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum UartBaudRate {
+    Rate9600 = 0,
+    Rate19200 = 1,
+    Rate38400 = 2,
+    Rate57600 = 3,
+    Rate115200 = 4,
+    Rate230400 = 5,
+    Rate460800 = 6,
+    Rate921600 = 7,
+}
+
+impl UartBaudRate {
+    /// The baud rate in bits per second.
+    pub fn as_bps(&self) -> u32 {
+        match self {
+            UartBaudRate::Rate9600 => 9_600,
+            UartBaudRate::Rate19200 => 19_200,
+            UartBaudRate::Rate38400 => 38_400,
+            UartBaudRate::Rate57600 => 57_600,
+            UartBaudRate::Rate115200 => 115_200,
+            UartBaudRate::Rate230400 => 230_400,
+            UartBaudRate::Rate460800 => 460_800,
+            UartBaudRate::Rate921600 => 921_600,
+        }
+    }
+}
+
+impl TryFrom<u8> for UartBaudRate {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(UartBaudRate::Rate9600),
+            1 => Ok(UartBaudRate::Rate19200),
+            2 => Ok(UartBaudRate::Rate38400),
+            3 => Ok(UartBaudRate::Rate57600),
+            4 => Ok(UartBaudRate::Rate115200),
+            5 => Ok(UartBaudRate::Rate230400),
+            6 => Ok(UartBaudRate::Rate460800),
+            7 => Ok(UartBaudRate::Rate921600),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<UartBaudRate> for u8 {
+    fn from(rate: UartBaudRate) -> Self {
+        rate as u8
+    }
+}
+
+/// Set UART baud rate command.
+///
+/// The host must switch its own serial port to the new rate once the
+/// adapter has acknowledged the command.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SetUartBaud {
+    pub rate: UartBaudRate,
+}
+
+impl SetUartBaud {
+    pub fn new(rate: UartBaudRate) -> Self {
+        Self { rate }
+    }
+
+    /// Iterate over the encoded bytes of this command.
+    pub fn encoding_bytes(&self) -> EncodingBytes {
+        EncodingBytes::new(self)
+    }
+
+    /// Try parsing a [`SetUartBaud`] command from a string.
+    pub fn try_parse(input: &str) -> IResult<&str, Self> {
+        let (input, (_, rate, _)) = tuple((tag("U"), one_of("0123456789"), tag("\r")))(input)?;
+
+        let rate = UartBaudRate::try_from(rate as u8 - b'0')
+            .map_err(|_| Err::Failure(Error::new(input, ErrorKind::Digit)))?;
+
+        Ok((input, Self { rate }))
+    }
+}
+
+impl core::fmt::Display for SetUartBaud {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "U{}\r", self.rate as u8)
+    }
+}
+
+/// Set acceptance code command.
+///
+/// Sets the 32-bit acceptance code register used for hardware identifier
+/// filtering. Must only be sent while the channel is closed.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SetAcceptanceCode {
+    pub code: u32,
+}
+
+impl SetAcceptanceCode {
+    pub fn new(code: u32) -> Self {
+        Self { code }
+    }
+
+    /// Iterate over the encoded bytes of this command.
+    pub fn encoding_bytes(&self) -> EncodingBytes {
+        EncodingBytes::new(self)
+    }
+
+    /// Try parsing a [`SetAcceptanceCode`] command from a string.
+    pub fn try_parse(input: &str) -> IResult<&str, Self> {
+        let (input, (_, code, _)) = tuple((tag("M"), hex_number(8), tag("\r")))(input)?;
+        Ok((input, Self { code }))
+    }
+}
+
+impl core::fmt::Display for SetAcceptanceCode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "M{:08X}\r", self.code)
+    }
+}
+
+/// Set acceptance mask command.
+///
+/// Sets the 32-bit acceptance mask register paired with
+/// [`SetAcceptanceCode`]. A `1` bit means "don't care" for the corresponding
+/// code bit, so `0xFFFFFFFF` accepts all frames and `0x00000000` only accepts
+/// an exact match. Must only be sent while the channel is closed.
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SetAcceptanceMask {
+    pub mask: u32,
+}
+
+impl SetAcceptanceMask {
+    pub fn new(mask: u32) -> Self {
+        Self { mask }
+    }
+
+    /// Iterate over the encoded bytes of this command.
+    pub fn encoding_bytes(&self) -> EncodingBytes {
+        EncodingBytes::new(self)
+    }
+
+    /// Try parsing a [`SetAcceptanceMask`] command from a string.
+    pub fn try_parse(input: &str) -> IResult<&str, Self> {
+        let (input, (_, mask, _)) = tuple((tag("m"), hex_number(8), tag("\r")))(input)?;
+        Ok((input, Self { mask }))
+    }
+}
+
+impl core::fmt::Display for SetAcceptanceMask {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "m{:08X}\r", self.mask)
+    }
+}
+
+/// Transmit frame command.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Transmit {
     frame: Frame,
 }
@@ -133,93 +678,230 @@ impl Transmit {
         Self { frame }
     }
 
-    /// Try parsing a [`Transmit`] command from a string.
-    pub fn try_parse(input: &str) -> IResult<&str, Self> {
-        let (input, kind) = one_of("tTrR")(input)?;
-        let (input, id) = match kind {
-            't' | 'r' => {
-                let (input, id_hex) = take(3_usize)(input)?;
-                let id = u16::from_str_radix(id_hex, 16)
-                    .map_err(|_| Err::Failure(Error::new(input, ErrorKind::HexDigit)))?;
-                (input, Id::Standard(StandardId::new(id).unwrap()))
-            }
-            'T' | 'R' => {
-                let (input, id_hex) = take(8_usize)(input)?;
-                let id = u32::from_str_radix(id_hex, 16)
-                    .map_err(|_| Err::Failure(Error::new(input, ErrorKind::HexDigit)))?;
-                (input, Id::Extended(ExtendedId::new(id).unwrap()))
-            }
-            _ => unreachable!(), // other cases are impossible due to `one_of`
-        };
+    /// Create a [`Transmit`] for each of `frames`.
+    ///
+    /// At most `N` commands are created. Any frames beyond that are silently
+    /// dropped.
+    #[cfg(feature = "heapless")]
+    pub fn new_batch<'a, const N: usize>(
+        frames: impl IntoIterator<Item = &'a Frame>,
+    ) -> heapless::Vec<Transmit, N> {
+        frames.into_iter().take(N).map(Transmit::new).collect()
+    }
 
-        let (input, dlc) = take(1_usize)(input)?;
-        let dlc = usize::from_str_radix(dlc, 16)
-            .map_err(|_| Err::Failure(Error::new(input, ErrorKind::HexDigit)))?;
+    /// Encode each of `frames` into its own `M` byte buffer.
+    ///
+    /// Encoded commands are terminated by `\r` and the rest of each buffer is
+    /// zero filled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `M` is less than 27, the longest encoded [`Transmit`].
+    pub fn batch_encode<const N: usize, const M: usize>(frames: &[Frame; N]) -> [[u8; M]; N] {
+        assert!(
+            M >= MAX_FRAME_ENCODED_LEN,
+            "buffer shorter than MAX_FRAME_ENCODED_LEN"
+        );
 
-        let (input, data) = if dlc > 0 {
-            take(dlc * 2_usize)(input)?
-        } else {
-            (input, "")
-        };
+        let mut encoded = [[0; M]; N];
 
-        let data = if data.is_empty() {
-            [0; 8]
-        } else {
-            let mut array = [0; 8];
-            for i in 0..dlc {
-                array[i] = u8::from_str_radix(&data[i * 2..i * 2 + 2], 16)
-                    .map_err(|_| Err::Failure(Error::new(input, ErrorKind::HexDigit)))?;
-            }
-            array
-        };
+        for (buf, frame) in encoded.iter_mut().zip(frames) {
+            let mut writer = encode::SliceWriter::new(buf);
+            // Buffers are at least MAX_FRAME_ENCODED_LEN long so this cannot fail.
+            write!(writer, "{}", Transmit::new(frame)).unwrap();
+        }
+
+        encoded
+    }
+
+    /// The first `N` data bytes of the frame, or `None` if the frame carries
+    /// fewer than `N` bytes.
+    pub fn data_slice_exact<const N: usize>(&self) -> Option<&[u8; N]> {
+        self.frame.data().get(..N)?.try_into().ok()
+    }
+
+    /// Check if the frame carries the identifier `id`.
+    pub fn id_matches(&self, id: impl Into<Id>) -> bool {
+        self.frame.id() == id.into()
+    }
+
+    /// Check if the numeric value of the frame identifier is `raw`,
+    /// regardless of whether it is standard or extended.
+    pub fn raw_id_matches(&self, raw: u32) -> bool {
+        self.frame.id_raw() == raw
+    }
+
+    /// Check if this frame answers `request` under the OBD-II convention,
+    /// where a request on standard identifier X is answered on X + 8, for
+    /// example 0x7E0 and 0x7E8.
+    ///
+    /// Always `false` if either frame has an extended identifier.
+    pub fn is_response_to(&self, request: &Transmit) -> bool {
+        match (self.frame.id(), request.frame.id()) {
+            (Id::Standard(id), Id::Standard(request)) => id.as_raw() == request.as_raw() + 8,
+            _ => false,
+        }
+    }
+
+    /// Zero length data frame on identifier 0x000, the canonical "null frame"
+    /// for testing adapters. Same as [`NULL_FRAME`].
+    pub const fn zero_dlc() -> Transmit {
+        Transmit {
+            frame: Frame::new_empty(Id::Standard(StandardId::ZERO)),
+        }
+    }
 
-        let frame = if kind == 't' || kind == 'T' {
-            Frame::new(id, &data[..dlc]).unwrap()
+    /// The frame expected back from an adapter in loopback mode after sending
+    /// this command, which is identical to the one sent.
+    pub fn new_loopback_echo(&self) -> Transmit {
+        *self
+    }
+
+    /// The frame carried by this command.
+    pub fn frame(&self) -> &Frame {
+        &self.frame
+    }
+
+    /// Consume this command, returning the frame it carries.
+    pub fn into_frame(self) -> Frame {
+        self.frame
+    }
+
+    /// Iterate over the encoded bytes of this command.
+    pub fn encoding_bytes(&self) -> EncodingBytes {
+        EncodingBytes::new(self)
+    }
+
+    /// Create a data frame [`Transmit`] from its hex encoded identifier and
+    /// data, as they appear after the `t` or `T` command letter.
+    ///
+    /// `id` must be 3 hex digits for a standard identifier or 8 hex digits for
+    /// an extended identifier. `data` is 0 to 16 hex digits.
+    pub fn try_from_hex_parts(id: &str, data: &str) -> Result<Transmit, ParseError> {
+        if id.len() != 3 && id.len() != 8 {
+            return Err(ParseError::InvalidIdLength);
+        }
+        if !data.len().is_multiple_of(2) || data.len() > 16 {
+            return Err(ParseError::InvalidDataLength);
+        }
+        if !is_hex(id) || !is_hex(data) {
+            return Err(ParseError::InvalidHex);
+        }
+
+        let raw = u32::from_str_radix(id, 16).map_err(|_| ParseError::InvalidHex)?;
+        let id = if id.len() == 3 {
+            StandardId::new(raw as u16).map(Id::Standard)
         } else {
-            Frame::new_remote(id, dlc).unwrap()
-        };
+            ExtendedId::new(raw).map(Id::Extended)
+        }
+        .ok_or(ParseError::InvalidId)?;
+
+        let mut bytes = [0; 8];
+        let len = data.len() / 2;
+        for (i, byte) in bytes[..len].iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&data[i * 2..i * 2 + 2], 16)
+                .map_err(|_| ParseError::InvalidHex)?;
+        }
 
-        let (input, _) = tag("\r")(input)?;
+        let frame = Frame::new(id, &bytes[..len]).unwrap();
+
+        Ok(Self { frame })
+    }
+
+    /// Try parsing a [`Transmit`] command from a string.
+    pub fn try_parse(input: &str) -> IResult<&str, Self> {
+        let (input, (frame, _)) = tuple((parse_frame, tag("\r")))(input)?;
 
         Ok((input, Self::new(&frame)))
     }
-}
 
-impl core::fmt::Display for Transmit {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let cmd = match (self.frame.is_extended(), self.frame.is_remote_frame()) {
-            (false, false) => 't',
-            (true, false) => 'T',
-            (true, true) => 'R',
-            (false, true) => 'r',
-        };
+    /// Try parsing a [`Transmit`] command from a byte slice.
+    ///
+    /// Equivalent to [`Transmit::try_parse`] without requiring the caller to
+    /// validate the input as UTF-8 first.
+    pub fn try_parse_bytes(input: &[u8]) -> IResult<&[u8], Self> {
+        let (input, (frame, _)) = tuple((parse_frame_bytes, tag("\r")))(input)?;
 
-        match self.frame.id() {
-            Id::Standard(id) => write!(f, "{}{:03X}", cmd, id.as_raw())?,
-            Id::Extended(id) => write!(f, "{}{:08X}", cmd, id.as_raw())?,
-        }
+        Ok((input, Self::new(&frame)))
+    }
 
-        write!(f, "{}", self.frame.dlc())?;
+    /// Try parsing a [`Transmit`] command from the start of a byte buffer.
+    ///
+    /// Returns the command and the number of bytes consumed, including the
+    /// `\r` terminator, so a read buffer can be advanced past it. Returns
+    /// [`ParseError::Incomplete`] until the terminator has been received.
+    pub fn try_parse_slice(input: &[u8]) -> Result<(Transmit, usize), ParseError> {
+        let end = input
+            .iter()
+            .position(|&b| b == b'\r')
+            .ok_or(ParseError::Incomplete)?;
 
-        if self.frame.is_data_frame() {
-            for byte in self.frame.data() {
-                write!(f, "{:02X}", *byte)?;
-            }
+        match Self::try_parse_bytes(input) {
+            Ok((rest, transmit)) => Ok((transmit, input.len() - rest.len())),
+            Err(_) => Err(transmit_error(&input[..=end])),
         }
+    }
+}
 
-        write!(f, "\r")?;
+impl core::fmt::Display for Transmit {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write_frame(f, &self.frame, false)?;
+        write!(f, "\r")
+    }
+}
 
-        Ok(())
+/// Same encoding as [`core::fmt::Display`], with lowercase hex digits.
+impl core::fmt::LowerHex for Transmit {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write_frame(f, &self.frame, true)?;
+        write!(f, "\r")
     }
 }
 
+/// Zero length data frame on identifier 0x000, see [`Transmit::zero_dlc`].
+pub const NULL_FRAME: Transmit = Transmit::zero_dlc();
+
 /// Command variants.
+///
+/// An adapter moves through the following states:
+///
+/// ```text
+/// unconfigured --Setup--> configured --Open----------> open
+///      ^                      |                          |
+///      |                      +--OpenListenOnly--> listen-only
+///      |                                                 |
+///      +---------------------Close-----------------------+
+/// ```
+///
+/// [`SetupBtr`] can be sent in place of [`Setup`] to configure custom bit
+/// timing. [`SetTimestampMode`], [`SetUartBaud`], [`SetAcceptanceCode`] and
+/// [`SetAcceptanceMask`] are only accepted while the port is closed.
+/// [`Transmit`] is only valid while the port is open. The default command is
+/// [`Close`], which is always safe to send and puts the adapter back into the
+/// unconfigured state.
+///
+/// While timestamps are on, received frames arrive as [`FrameTimestamp`]
+/// instead of [`Transmit`].
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Command {
     Setup(Setup),
     Open(Open),
     Close(Close),
     Transmit(Transmit),
+    ListenOnly(OpenListenOnly),
+    SetupBtr(SetupBtr),
+    SetTimestampMode(SetTimestampMode),
+    TimestampedFrame(FrameTimestamp),
+    ReadStatusFlags(ReadStatusFlags),
+    StatusFlags(StatusFlagsResponse),
+    GetVersion(GetVersion),
+    Version(VersionResponse),
+    GetSerialNumber(GetSerialNumber),
+    SerialNumber(SerialNumberResponse),
+    SetUartBaud(SetUartBaud),
+    SetAcceptanceCode(SetAcceptanceCode),
+    SetAcceptanceMask(SetAcceptanceMask),
 }
 
 impl Command {
@@ -227,29 +909,539 @@ impl Command {
     pub fn try_parse(input: &str) -> IResult<&str, Self> {
         alt((
             map(Setup::try_parse, Command::Setup),
+            map(SetupBtr::try_parse, Command::SetupBtr),
             map(Open::try_parse, Command::Open),
+            map(OpenListenOnly::try_parse, Command::ListenOnly),
             map(Close::try_parse, Command::Close),
+            map(SetTimestampMode::try_parse, Command::SetTimestampMode),
+            map(ReadStatusFlags::try_parse, Command::ReadStatusFlags),
+            map(StatusFlagsResponse::try_parse, Command::StatusFlags),
+            map(GetVersion::try_parse, Command::GetVersion),
+            map(VersionResponse::try_parse, Command::Version),
+            map(GetSerialNumber::try_parse, Command::GetSerialNumber),
+            map(SerialNumberResponse::try_parse, Command::SerialNumber),
+            map(SetUartBaud::try_parse, Command::SetUartBaud),
+            map(SetAcceptanceCode::try_parse, Command::SetAcceptanceCode),
+            map(SetAcceptanceMask::try_parse, Command::SetAcceptanceMask),
             map(Transmit::try_parse, Command::Transmit),
+            map(FrameTimestamp::try_parse, Command::TimestampedFrame),
         ))(input)
     }
-}
 
-impl core::fmt::Display for Command {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    /// Try parsing a [`Command`] from a byte slice.
+    ///
+    /// Equivalent to [`Command::try_parse`] without requiring the caller to
+    /// validate the input as UTF-8 first.
+    pub fn try_parse_bytes(input: &[u8]) -> IResult<&[u8], Self> {
+        parse_ascii(input, Self::try_parse)
+    }
+
+    /// Iterate over the encoded bytes of this command.
+    pub fn encoding_bytes(&self) -> EncodingBytes {
         match self {
-            Command::Setup(setup) => setup.fmt(f),
-            Command::Open(open) => open.fmt(f),
-            Command::Close(close) => close.fmt(f),
-            Command::Transmit(transmit) => transmit.fmt(f),
+            Command::Setup(setup) => setup.encoding_bytes(),
+            Command::Open(open) => open.encoding_bytes(),
+            Command::Close(close) => close.encoding_bytes(),
+            Command::Transmit(transmit) => transmit.encoding_bytes(),
+            Command::ListenOnly(listen) => listen.encoding_bytes(),
+            Command::SetupBtr(setup) => setup.encoding_bytes(),
+            Command::SetTimestampMode(timestamp) => timestamp.encoding_bytes(),
+            Command::TimestampedFrame(frame) => frame.encoding_bytes(),
+            Command::ReadStatusFlags(read) => read.encoding_bytes(),
+            Command::StatusFlags(flags) => flags.encoding_bytes(),
+            Command::GetVersion(get) => get.encoding_bytes(),
+            Command::Version(version) => version.encoding_bytes(),
+            Command::GetSerialNumber(get) => get.encoding_bytes(),
+            Command::SerialNumber(serial) => serial.encoding_bytes(),
+            Command::SetUartBaud(baud) => baud.encoding_bytes(),
+            Command::SetAcceptanceCode(code) => code.encoding_bytes(),
+            Command::SetAcceptanceMask(mask) => mask.encoding_bytes(),
         }
     }
-}
 
-#[cfg(test)]
+    /// The [`CommandKind`] of this command.
+    pub fn kind(&self) -> CommandKind {
+        match self {
+            Command::Setup(_) => CommandKind::Setup,
+            Command::Open(_) => CommandKind::Open,
+            Command::Close(_) => CommandKind::Close,
+            Command::Transmit(_) => CommandKind::Transmit,
+            Command::ListenOnly(_) => CommandKind::ListenOnly,
+            Command::SetupBtr(_) => CommandKind::SetupBtr,
+            Command::SetTimestampMode(_) => CommandKind::SetTimestampMode,
+            Command::TimestampedFrame(_) => CommandKind::TimestampedFrame,
+            Command::ReadStatusFlags(_) => CommandKind::ReadStatusFlags,
+            Command::StatusFlags(_) => CommandKind::StatusFlags,
+            Command::GetVersion(_) => CommandKind::GetVersion,
+            Command::Version(_) => CommandKind::Version,
+            Command::GetSerialNumber(_) => CommandKind::GetSerialNumber,
+            Command::SerialNumber(_) => CommandKind::SerialNumber,
+            Command::SetUartBaud(_) => CommandKind::SetUartBaud,
+            Command::SetAcceptanceCode(_) => CommandKind::SetAcceptanceCode,
+            Command::SetAcceptanceMask(_) => CommandKind::SetAcceptanceMask,
+        }
+    }
+}
+
+impl Default for Command {
+    fn default() -> Self {
+        Command::Close(Close::new())
+    }
+}
+
+impl core::fmt::Display for Command {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Command::Setup(setup) => setup.fmt(f),
+            Command::Open(open) => open.fmt(f),
+            Command::Close(close) => close.fmt(f),
+            Command::Transmit(transmit) => transmit.fmt(f),
+            Command::ListenOnly(listen) => listen.fmt(f),
+            Command::SetupBtr(setup) => setup.fmt(f),
+            Command::SetTimestampMode(timestamp) => timestamp.fmt(f),
+            Command::TimestampedFrame(frame) => frame.fmt(f),
+            Command::ReadStatusFlags(read) => read.fmt(f),
+            Command::StatusFlags(flags) => flags.fmt(f),
+            Command::GetVersion(get) => get.fmt(f),
+            Command::Version(version) => version.fmt(f),
+            Command::GetSerialNumber(get) => get.fmt(f),
+            Command::SerialNumber(serial) => serial.fmt(f),
+            Command::SetUartBaud(baud) => baud.fmt(f),
+            Command::SetAcceptanceCode(code) => code.fmt(f),
+            Command::SetAcceptanceMask(mask) => mask.fmt(f),
+        }
+    }
+}
+
+/// Implement conversions between [`Command`] and the type carried by each
+/// variant.
+macro_rules! command_conversions {
+    ($($variant:ident($ty:ty),)*) => {
+        $(
+            impl From<$ty> for Command {
+                fn from(command: $ty) -> Self {
+                    Command::$variant(command)
+                }
+            }
+
+            /// Returns the original command if it is a different variant.
+            impl TryFrom<Command> for $ty {
+                type Error = Command;
+
+                fn try_from(command: Command) -> Result<Self, Self::Error> {
+                    match command {
+                        Command::$variant(inner) => Ok(inner),
+                        other => Err(other),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+command_conversions! {
+    Setup(Setup),
+    Open(Open),
+    Close(Close),
+    Transmit(Transmit),
+    ListenOnly(OpenListenOnly),
+    SetupBtr(SetupBtr),
+    SetTimestampMode(SetTimestampMode),
+    TimestampedFrame(FrameTimestamp),
+    ReadStatusFlags(ReadStatusFlags),
+    StatusFlags(StatusFlagsResponse),
+    GetVersion(GetVersion),
+    Version(VersionResponse),
+    GetSerialNumber(GetSerialNumber),
+    SerialNumber(SerialNumberResponse),
+    SetUartBaud(SetUartBaud),
+    SetAcceptanceCode(SetAcceptanceCode),
+    SetAcceptanceMask(SetAcceptanceMask),
+}
+
+/// Command kinds, without the data carried by the command.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CommandKind {
+    Setup,
+    Open,
+    Close,
+    Transmit,
+    ListenOnly,
+    SetupBtr,
+    SetTimestampMode,
+    TimestampedFrame,
+    ReadStatusFlags,
+    StatusFlags,
+    GetVersion,
+    Version,
+    GetSerialNumber,
+    SerialNumber,
+    SetUartBaud,
+    SetAcceptanceCode,
+    SetAcceptanceMask,
+}
+
+impl core::fmt::Display for CommandKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let name = match self {
+            CommandKind::Setup => "Setup",
+            CommandKind::Open => "Open",
+            CommandKind::Close => "Close",
+            CommandKind::Transmit => "Transmit",
+            CommandKind::ListenOnly => "ListenOnly",
+            CommandKind::SetupBtr => "SetupBtr",
+            CommandKind::SetTimestampMode => "SetTimestampMode",
+            CommandKind::TimestampedFrame => "TimestampedFrame",
+            CommandKind::ReadStatusFlags => "ReadStatusFlags",
+            CommandKind::StatusFlags => "StatusFlags",
+            CommandKind::GetVersion => "GetVersion",
+            CommandKind::Version => "Version",
+            CommandKind::GetSerialNumber => "GetSerialNumber",
+            CommandKind::SerialNumber => "SerialNumber",
+            CommandKind::SetUartBaud => "SetUartBaud",
+            CommandKind::SetAcceptanceCode => "SetAcceptanceCode",
+            CommandKind::SetAcceptanceMask => "SetAcceptanceMask",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+/// Maximum length of a command, including the `\r` terminator and any
+/// timestamp, given its first byte.
+///
+/// Returns `None` if `first_byte` does not start a known command.
+pub fn slcan_command_max_len(first_byte: u8) -> Option<usize> {
+    match first_byte {
+        b'S' => Some(3),
+        b'O' | b'L' | b'C' => Some(2),
+        b't' => Some(26),
+        b'T' => Some(31),
+        b'r' => Some(10),
+        b'R' => Some(15),
+        b's' => Some(6),
+        b'Z' => Some(3),
+        b'F' => Some(4),
+        b'V' => Some(6),
+        b'N' => Some(6),
+        b'U' => Some(3),
+        b'M' => Some(10),
+        b'm' => Some(10),
+        _ => None,
+    }
+}
+
+/// Run a `&str` parser over the leading ASCII bytes of the first command in
+/// `input`, mapping the remainder and any error position back onto `input`.
+///
+/// Commands never contain `\r` or non-ASCII bytes, so neither can affect the
+/// result of the parser.
+fn parse_ascii<'a, T>(
+    input: &'a [u8],
+    parser: fn(&'a str) -> IResult<&'a str, T>,
+) -> IResult<&'a [u8], T> {
+    let end = input
+        .iter()
+        .position(|&b| b == b'\r')
+        .map_or(input.len(), |i| i + 1);
+    let len = input[..end]
+        .iter()
+        .position(|b| !b.is_ascii())
+        .unwrap_or(end);
+
+    // SAFETY: every byte in `input[..len]` is ASCII, which is valid UTF-8.
+    let ascii = unsafe { core::str::from_utf8_unchecked(&input[..len]) };
+    let offset = |rest: &str| len - rest.len();
+
+    match parser(ascii) {
+        Ok((rest, value)) => Ok((&input[offset(rest)..], value)),
+        Err(e) => Err(e.map(|e| Error::new(&input[offset(e.input)..], e.code))),
+    }
+}
+
+/// Classify why `line`, a `\r` terminated [`Transmit`] command, failed to
+/// parse by walking its tokens to the first problem.
+fn transmit_error(line: &[u8]) -> ParseError {
+    let mut tokens = SlcanLexer::new(line);
+
+    let (id_len, remote) = match tokens.next() {
+        Some(Ok(Token::CommandStart(b't'))) => (3, false),
+        Some(Ok(Token::CommandStart(b'T'))) => (8, false),
+        Some(Ok(Token::CommandStart(b'r'))) => (3, true),
+        Some(Ok(Token::CommandStart(b'R'))) => (8, true),
+        _ => return ParseError::UnknownCommand,
+    };
+
+    // Identifier digits followed by the DLC digit.
+    let mut id = 0;
+    let mut dlc = 0;
+    for i in 0..=id_len {
+        let digit = match tokens.next() {
+            Some(Ok(Token::Digit(digit))) => digit,
+            Some(Ok(Token::Terminator)) => return ParseError::InvalidIdLength,
+            _ => return ParseError::InvalidHex,
+        };
+        if i < id_len {
+            id = id << 4 | u32::from(digit);
+        } else {
+            dlc = usize::from(digit);
+        }
+    }
+
+    let mut len = 0;
+    loop {
+        match tokens.next() {
+            Some(Ok(Token::HexByte(_))) => len += 1,
+            Some(Ok(Token::Terminator)) => break,
+            // A lone hex digit before the terminator.
+            Some(Err(LexError { byte: b'\r', .. })) => return ParseError::InvalidDataLength,
+            _ => return ParseError::InvalidHex,
+        }
+    }
+
+    let valid_id = match id_len {
+        3 => id <= u32::from(StandardId::MAX.as_raw()),
+        _ => id <= ExtendedId::MAX.as_raw(),
+    };
+    if !valid_id {
+        return ParseError::InvalidId;
+    }
+
+    if dlc > 8 || len != if remote { 0 } else { dlc } {
+        return ParseError::InvalidDataLength;
+    }
+
+    ParseError::InvalidCommand
+}
+
+pub(crate) fn is_hex(s: &str) -> bool {
+    s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Write `len` hex digits of `value`.
+fn write_hex(
+    f: &mut core::fmt::Formatter<'_>,
+    value: u32,
+    len: usize,
+    lowercase: bool,
+) -> core::fmt::Result {
+    let digits = if lowercase {
+        b"0123456789abcdef"
+    } else {
+        b"0123456789ABCDEF"
+    };
+
+    for i in (0..len).rev() {
+        let nibble = (value >> (i * 4)) & 0xF;
+        f.write_char(digits[nibble as usize] as char)?;
+    }
+
+    Ok(())
+}
+
+/// Write a frame without the `\r` terminator.
+fn write_frame(
+    f: &mut core::fmt::Formatter<'_>,
+    frame: &Frame,
+    lowercase: bool,
+) -> core::fmt::Result {
+    let cmd = match (frame.is_extended(), frame.is_remote_frame()) {
+        (false, false) => 't',
+        (true, false) => 'T',
+        (true, true) => 'R',
+        (false, true) => 'r',
+    };
+
+    f.write_char(cmd)?;
+
+    match frame.id() {
+        Id::Standard(id) => write_hex(f, id.as_raw().into(), 3, lowercase)?,
+        Id::Extended(id) => write_hex(f, id.as_raw(), 8, lowercase)?,
+    }
+
+    write!(f, "{}", frame.dlc())?;
+
+    if frame.is_data_frame() {
+        for byte in frame.data() {
+            write_hex(f, (*byte).into(), 2, lowercase)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse a frame without the `\r` terminator.
+///
+/// Same as [`parse_frame_bytes`], which all frames are parsed with.
+fn parse_frame(input: &str) -> IResult<&str, Frame> {
+    // Every position the lexer stops at follows an ASCII byte, so it is a
+    // char boundary.
+    let rest = |bytes: &[u8]| &input[input.len() - bytes.len()..];
+
+    match parse_frame_bytes(input.as_bytes()) {
+        Ok((bytes, frame)) => Ok((rest(bytes), frame)),
+        Err(e) => Err(e.map(|e| Error::new(rest(e.input), e.code))),
+    }
+}
+
+/// Parse a frame without the `\r` terminator from the [`Token`]s of `input`.
+///
+/// Errors match [`hex_number`] fields: a field cut short by the end of the
+/// input is an `Eof` error and a field holding anything but hex digits is a
+/// `HexDigit` failure.
+fn parse_frame_bytes(input: &[u8]) -> IResult<&[u8], Frame> {
+    let mut tokens = SlcanLexer::new(input);
+    let error = |position: usize, kind| Error::new(&input[position..], kind);
+    let field_error = |start: usize, len: usize| {
+        if start + len > input.len() {
+            Err::Error(error(start, ErrorKind::Eof))
+        } else {
+            Err::Failure(error(start, ErrorKind::HexDigit))
+        }
+    };
+
+    let (id_len, remote) = match tokens.next() {
+        Some(Ok(Token::CommandStart(b't'))) => (3, false),
+        Some(Ok(Token::CommandStart(b'T'))) => (8, false),
+        Some(Ok(Token::CommandStart(b'r'))) => (3, true),
+        Some(Ok(Token::CommandStart(b'R'))) => (8, true),
+        _ => return Err(Err::Error(error(0, ErrorKind::OneOf))),
+    };
+
+    // Identifier digits, then the DLC digit.
+    let mut digits = |len: usize| {
+        let start = tokens.position();
+        let mut value = 0;
+        for _ in 0..len {
+            match tokens.next() {
+                Some(Ok(Token::Digit(digit))) => value = value << 4 | u32::from(digit),
+                _ => return Err(field_error(start, len)),
+            }
+        }
+        Ok(value)
+    };
+
+    let id = digits(id_len)?;
+    let id = match id_len {
+        3 => StandardId::new(id as u16).map(Id::Standard),
+        _ => ExtendedId::new(id).map(Id::Extended),
+    }
+    .ok_or(Err::Failure(error(1, ErrorKind::Verify)))?;
+
+    let dlc = digits(1)? as usize;
+    if dlc > 8 {
+        return Err(Err::Failure(error(tokens.position(), ErrorKind::Verify)));
+    }
+
+    let frame = if remote {
+        Frame::new_remote(id, dlc).unwrap()
+    } else {
+        let mut data = [0; 8];
+        for byte in &mut data[..dlc] {
+            let start = tokens.position();
+            match tokens.next() {
+                Some(Ok(Token::HexByte(value))) => *byte = value,
+                _ => return Err(field_error(start, 2)),
+            }
+        }
+        Frame::new(id, &data[..dlc]).unwrap()
+    };
+
+    Ok((&input[tokens.position()..], frame))
+}
+
+/// Parse exactly `len` hex digits as a number.
+fn hex_number(len: usize) -> impl Fn(&str) -> IResult<&str, u32> {
+    move |input| {
+        let (rest, digits) = take(len)(input)?;
+
+        if !is_hex(digits) {
+            return Err(Err::Failure(Error::new(input, ErrorKind::HexDigit)));
+        }
+
+        let number = u32::from_str_radix(digits, 16)
+            .map_err(|_| Err::Failure(Error::new(input, ErrorKind::HexDigit)))?;
+
+        Ok((rest, number))
+    }
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
     use embedded_can::{ExtendedId, StandardId};
 
+    #[test]
+    fn bitrate_automotive_standard() {
+        assert!(!Bitrate::Rate100kbit.is_automotive_standard());
+        assert!(Bitrate::Rate125kbit.is_automotive_standard());
+        assert!(Bitrate::Rate250kbit.is_automotive_standard());
+        assert!(Bitrate::Rate500kbit.is_automotive_standard());
+        assert!(!Bitrate::Rate800kbit.is_automotive_standard());
+        assert!(Bitrate::Rate1000kbit.is_automotive_standard());
+    }
+
+    #[test]
+    fn bitrate_conversions() {
+        let bitrates = [
+            (Bitrate::Rate10kbit, 10_000_u32),
+            (Bitrate::Rate20kbit, 20_000),
+            (Bitrate::Rate50kbit, 50_000),
+            (Bitrate::Rate100kbit, 100_000),
+            (Bitrate::Rate125kbit, 125_000),
+            (Bitrate::Rate250kbit, 250_000),
+            (Bitrate::Rate500kbit, 500_000),
+            (Bitrate::Rate800kbit, 800_000),
+            (Bitrate::Rate1000kbit, 1_000_000),
+        ];
+
+        for (index, (bitrate, bps)) in bitrates.into_iter().enumerate() {
+            assert_eq!(Bitrate::try_from(index as u8), Ok(bitrate));
+            assert_eq!(u8::from(bitrate), index as u8);
+            assert_eq!(Bitrate::try_from(bps), Ok(bitrate));
+        }
+
+        assert_eq!(Bitrate::try_from(9_u8), Err(()));
+        assert_eq!(Bitrate::try_from(u8::MAX), Err(()));
+        assert_eq!(Bitrate::try_from(0_u32), Err(()));
+        assert_eq!(Bitrate::try_from(1_000_001_u32), Err(()));
+        assert_eq!(Bitrate::try_from(5_u32), Err(()));
+    }
+
+    #[test]
+    fn bitrate_bps() {
+        assert!(Bitrate::ALL
+            .iter()
+            .all(|b| Bitrate::try_from_bps(b.as_bps()) == Some(*b)));
+
+        for (bitrate, bps) in Bitrate::ALL.iter().zip(Bitrate::ALL_BPS) {
+            assert_eq!(bitrate.as_bps(), bps);
+        }
+        assert!(Bitrate::ALL_BPS.windows(2).all(|w| w[0] < w[1]));
+
+        assert_eq!(Bitrate::Rate500kbit.as_bps(), 500_000);
+        assert_eq!(Bitrate::try_from_bps(333_000), None);
+    }
+
+    #[test]
+    fn bitrate_description() {
+        assert_eq!(Bitrate::Rate10kbit.description(), "10 kbit/s");
+        assert_eq!(Bitrate::Rate500kbit.description(), "500 kbit/s");
+        assert_eq!(Bitrate::Rate1000kbit.description(), "1000 kbit/s");
+    }
+
+    #[test]
+    fn bitrate_bit_time() {
+        assert_eq!(Bitrate::Rate10kbit.nominal_bit_time_ns(), 100_000);
+        assert_eq!(Bitrate::Rate125kbit.nominal_bit_time_ns(), 8_000);
+        assert_eq!(Bitrate::Rate800kbit.nominal_bit_time_ns(), 1_250);
+        assert_eq!(Bitrate::Rate1000kbit.nominal_bit_time_ns(), 1_000);
+
+        assert_eq!(Bitrate::intermission_bits(), 3);
+        assert_eq!(Bitrate::Rate500kbit.intermission_ns(), 6_000);
+    }
+
     #[test]
     fn format_setup() {
         let setup = Setup::new(Bitrate::Rate10kbit);
@@ -273,6 +1465,162 @@ mod tests {
         assert!(Setup::try_parse("S \r").is_err());
     }
 
+    #[test]
+    fn format_setup_btr() {
+        assert_eq!(format!("{}", SetupBtr::new(0x00, 0x00)), "s0000\r");
+        assert_eq!(format!("{}", SetupBtr::new(0x03, 0x1C)), "s031C\r");
+        assert_eq!(format!("{}", SetupBtr::new(0xFF, 0xFF)), "sFFFF\r");
+    }
+
+    #[test]
+    fn parse_setup_btr() {
+        assert_eq!(
+            SetupBtr::try_parse("s0000\r"),
+            Ok(("", SetupBtr::new(0x00, 0x00)))
+        );
+        assert_eq!(
+            SetupBtr::try_parse("sFFFF\r"),
+            Ok(("", SetupBtr::new(0xFF, 0xFF)))
+        );
+        assert_eq!(
+            SetupBtr::try_parse("s031c\r"),
+            Ok(("", SetupBtr::new(0x03, 0x1C)))
+        );
+
+        assert!(SetupBtr::try_parse("s000\r").is_err());
+        assert!(SetupBtr::try_parse("s00000\r").is_err());
+        assert!(SetupBtr::try_parse("s0G00\r").is_err());
+        assert!(SetupBtr::try_parse("s+100\r").is_err());
+        assert!(SetupBtr::try_parse("S0000\r").is_err());
+    }
+
+    #[test]
+    fn format_set_timestamp_mode() {
+        assert_eq!(
+            format!("{}", SetTimestampMode::new(TimestampMode::Off)),
+            "Z0\r"
+        );
+        assert_eq!(
+            format!("{}", SetTimestampMode::new(TimestampMode::On)),
+            "Z1\r"
+        );
+    }
+
+    #[test]
+    fn parse_set_timestamp_mode() {
+        assert_eq!(
+            SetTimestampMode::try_parse("Z0\r"),
+            Ok(("", SetTimestampMode::new(TimestampMode::Off)))
+        );
+        assert_eq!(
+            SetTimestampMode::try_parse("Z1\r"),
+            Ok(("", SetTimestampMode::new(TimestampMode::On)))
+        );
+
+        assert!(SetTimestampMode::try_parse("Z2\r").is_err());
+        assert!(SetTimestampMode::try_parse("Z\r").is_err());
+        assert!(SetTimestampMode::try_parse("z1\r").is_err());
+    }
+
+    #[test]
+    fn timestamp_mode_try_from() {
+        assert_eq!(TimestampMode::try_from(0), Ok(TimestampMode::Off));
+        assert_eq!(TimestampMode::try_from(1), Ok(TimestampMode::On));
+        assert_eq!(TimestampMode::try_from(2), Err(()));
+    }
+
+    #[test]
+    fn set_uart_baud() {
+        assert_eq!(
+            format!("{}", SetUartBaud::new(UartBaudRate::Rate9600)),
+            "U0\r"
+        );
+        assert_eq!(
+            format!("{}", SetUartBaud::new(UartBaudRate::Rate921600)),
+            "U7\r"
+        );
+
+        for n in 0..=7 {
+            let rate = UartBaudRate::try_from(n).unwrap();
+            assert_eq!(u8::from(rate), n);
+
+            let encoded = format!("{}", SetUartBaud::new(rate));
+            assert_eq!(
+                SetUartBaud::try_parse(&encoded),
+                Ok(("", SetUartBaud::new(rate)))
+            );
+        }
+
+        assert_eq!(UartBaudRate::Rate115200.as_bps(), 115_200);
+        assert_eq!(UartBaudRate::try_from(8), Err(()));
+
+        assert!(matches!(
+            SetUartBaud::try_parse("U8\r"),
+            Err(Err::Failure(_))
+        ));
+        assert!(matches!(
+            SetUartBaud::try_parse("U9\r"),
+            Err(Err::Failure(_))
+        ));
+        for input in ["U10\r", "U007\r", "U00\r", "U\r"] {
+            assert!(SetUartBaud::try_parse(input).is_err(), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn set_acceptance_code() {
+        for (input, code) in [("M00000000\r", 0), ("M1FFFFFFF\r", 0x1FFFFFFF)] {
+            let cmd = SetAcceptanceCode::new(code);
+            assert_eq!(SetAcceptanceCode::try_parse(input), Ok(("", cmd)));
+            assert_eq!(format!("{}", cmd), input);
+        }
+
+        assert_eq!(
+            SetAcceptanceCode::try_parse("Mdeadbeef\r"),
+            Ok(("", SetAcceptanceCode::new(0xDEADBEEF)))
+        );
+
+        assert!(SetAcceptanceCode::try_parse("M0000000\r").is_err());
+        assert!(SetAcceptanceCode::try_parse("M000000000\r").is_err());
+        assert!(SetAcceptanceCode::try_parse("M0000000G\r").is_err());
+        assert!(SetAcceptanceCode::try_parse("m00000000\r").is_err());
+    }
+
+    #[test]
+    fn set_acceptance_mask() {
+        assert_eq!(
+            format!("{}", SetAcceptanceMask::new(0x1FFFFFFF)),
+            "m1FFFFFFF\r"
+        );
+        assert_eq!(
+            SetAcceptanceMask::try_parse("mdeadbeef\r"),
+            Ok(("", SetAcceptanceMask::new(0xDEADBEEF)))
+        );
+
+        assert!(SetAcceptanceMask::try_parse("m0000000\r").is_err());
+        assert!(SetAcceptanceMask::try_parse("m000000000\r").is_err());
+        assert!(SetAcceptanceMask::try_parse("M00000000\r").is_err());
+    }
+
+    #[test]
+    fn acceptance_filter_accept_all() {
+        let commands = [
+            Command::SetAcceptanceCode(SetAcceptanceCode::new(0x00000000)),
+            Command::SetAcceptanceMask(SetAcceptanceMask::new(0xFFFFFFFF)),
+        ];
+
+        let mut encoded = String::new();
+        for cmd in commands {
+            encoded.push_str(&format!("{}", cmd));
+        }
+        assert_eq!(encoded, "M00000000\rmFFFFFFFF\r");
+
+        let (rest, code) = Command::try_parse(&encoded).unwrap();
+        let (rest, mask) = Command::try_parse(rest).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!([code, mask], commands);
+    }
+
     #[test]
     fn format_open() {
         let open = Open::new();
@@ -286,6 +1634,23 @@ mod tests {
         assert!(Close::try_parse("o\r").is_err());
     }
 
+    #[test]
+    fn format_open_listen_only() {
+        let listen = OpenListenOnly::new();
+        assert_eq!(format!("{}", listen), "L\r");
+    }
+
+    #[test]
+    fn parse_open_listen_only() {
+        assert_eq!(
+            OpenListenOnly::try_parse("L\r"),
+            Ok(("", OpenListenOnly {}))
+        );
+
+        assert!(OpenListenOnly::try_parse("l\r").is_err());
+        assert!(OpenListenOnly::try_parse("L").is_err());
+    }
+
     #[test]
     fn format_close() {
         let close = Close::new();
@@ -326,6 +1691,141 @@ mod tests {
         assert_eq!(format!("{}", transmit), "r1230\r");
     }
 
+    #[test]
+    fn transmit_frame_accessors() {
+        let standard = Id::Standard(StandardId::new(0x123).unwrap());
+        let extended = Id::Extended(ExtendedId::new(0x12ABCDEF).unwrap());
+
+        for (input, id, remote, data) in [
+            ("t1232AA55\r", standard, false, &[0xAA, 0x55][..]),
+            ("T12ABCDEF1FF\r", extended, false, &[0xFF][..]),
+            ("r1232\r", standard, true, &[0, 0][..]),
+            ("R12ABCDEF0\r", extended, true, &[][..]),
+        ] {
+            let (_, transmit) = Transmit::try_parse(input).unwrap();
+
+            let frame = transmit.frame();
+            assert_eq!(frame.id(), id);
+            assert_eq!(frame.is_remote_frame(), remote);
+            assert_eq!(frame.dlc(), data.len());
+            if !remote {
+                assert_eq!(frame.data(), data);
+            }
+
+            assert_eq!(transmit.into_frame(), *frame);
+        }
+    }
+
+    #[test]
+    fn transmit_try_parse_slice() {
+        let frame = Frame::new(StandardId::new(0x123).unwrap(), &[0xAA, 0x55]).unwrap();
+        assert_eq!(
+            Transmit::try_parse_slice(b"t1232AA55\rt123"),
+            Ok((Transmit::new(&frame), 10))
+        );
+
+        let frame = Frame::new_remote(ExtendedId::new(0x12ABCDEF).unwrap(), 8).unwrap();
+        assert_eq!(
+            Transmit::try_parse_slice(b"R12ABCDEF8\r"),
+            Ok((Transmit::new(&frame), 11))
+        );
+
+        for (input, error) in [
+            (&b"t1232AA55"[..], ParseError::Incomplete),
+            (b"", ParseError::Incomplete),
+            (b"\r", ParseError::UnknownCommand),
+            (b"O\r", ParseError::UnknownCommand),
+            (b"t12\r", ParseError::InvalidIdLength),
+            (b"t1G31AA\r", ParseError::InvalidHex),
+            (b"t1231\xAA\r", ParseError::InvalidHex),
+            (b"t8001AA\r", ParseError::InvalidId),
+            (b"t1239\r", ParseError::InvalidDataLength),
+            (b"t1232AA\r", ParseError::InvalidDataLength),
+            (b"r1232AA\r", ParseError::InvalidDataLength),
+        ] {
+            assert_eq!(Transmit::try_parse_slice(input), Err(error));
+        }
+    }
+
+    #[test]
+    fn try_parse_bytes_matches_try_parse() {
+        fn as_bytes<T>(result: IResult<&str, T>) -> IResult<&[u8], T> {
+            match result {
+                Ok((rest, value)) => Ok((rest.as_bytes(), value)),
+                Err(e) => Err(e.map(|e| Error::new(e.input.as_bytes(), e.code))),
+            }
+        }
+
+        for input in [
+            "S6\r",
+            "S6\rO\r",
+            "S9\r",
+            "O\r",
+            "C\rC\r",
+            "t1232AA55\rt123",
+            "R12ABCDEF8\r",
+            "t1G31AA\r",
+            "t8001AA\r",
+            "F\r",
+            "x\r",
+            "",
+        ] {
+            let bytes = input.as_bytes();
+            assert_eq!(
+                Command::try_parse_bytes(bytes),
+                as_bytes(Command::try_parse(input))
+            );
+            assert_eq!(
+                Setup::try_parse_bytes(bytes),
+                as_bytes(Setup::try_parse(input))
+            );
+            assert_eq!(
+                Open::try_parse_bytes(bytes),
+                as_bytes(Open::try_parse(input))
+            );
+            assert_eq!(
+                Close::try_parse_bytes(bytes),
+                as_bytes(Close::try_parse(input))
+            );
+            assert_eq!(
+                Transmit::try_parse_bytes(bytes),
+                as_bytes(Transmit::try_parse(input))
+            );
+        }
+
+        assert_eq!(
+            Open::try_parse_bytes(b"O\r\xFF\xFE"),
+            Ok((&b"\xFF\xFE"[..], Open::new()))
+        );
+        assert!(Transmit::try_parse_bytes(b"t1231\xAA\r").is_err());
+    }
+
+    #[test]
+    fn transmit_zero_dlc() {
+        let frame = Frame::new(StandardId::ZERO, &[]).unwrap();
+        assert_eq!(Transmit::zero_dlc(), Transmit::new(&frame));
+        assert_eq!(NULL_FRAME, Transmit::zero_dlc());
+        assert_eq!(format!("{}", NULL_FRAME), "t0000\r");
+    }
+
+    #[test]
+    fn format_transmit_lower_hex() {
+        let frame = Frame::new(
+            Id::Extended(ExtendedId::new(0x12ABCDEF).unwrap()),
+            &[0xAA, 0x55],
+        )
+        .unwrap();
+        let transmit = Transmit::new(&frame);
+        assert_eq!(format!("{:x}", transmit), "T12abcdef2aa55\r");
+        assert_eq!(
+            Transmit::try_parse(&format!("{:x}", transmit)),
+            Ok(("", transmit))
+        );
+
+        let frame = Frame::new_remote(StandardId::new(0x7FF).unwrap(), 8).unwrap();
+        assert_eq!(format!("{:x}", Transmit::new(&frame)), "r7ff8\r");
+    }
+
     #[test]
     fn parse_transmit() {
         assert_eq!(
@@ -375,20 +1875,860 @@ mod tests {
                 )
             ))
         );
-    }
 
-    #[test]
-    fn format_command() {
-        let cmd = Command::Open(Open::new());
-        assert_eq!(format!("{}", cmd), "O\r");
+        assert_eq!(
+            Transmit::try_parse("R12ABCDEF4\r"),
+            Ok((
+                "",
+                Transmit::new(
+                    &Frame::new_remote(Id::Extended(ExtendedId::new(0x12ABCDEF).unwrap()), 4)
+                        .unwrap()
+                )
+            ))
+        );
+
+        assert!(Transmit::try_parse("t8000\r").is_err());
+        assert!(Transmit::try_parse("T200000000\r").is_err());
+        assert!(Transmit::try_parse("t1239112233445566778899\r").is_err());
+    }
+
+    #[test]
+    fn parse_transmit_hex_case() {
+        let standard = StandardId::new(0x123).unwrap();
+        let extended = ExtendedId::new(0x12ABCDEF).unwrap();
+        let data = Transmit::new(&Frame::new(standard, &[0xAA, 0xBB]).unwrap());
+        let ext_data = Transmit::new(&Frame::new(extended, &[0xAA, 0xBB]).unwrap());
+        let remote = Transmit::new(&Frame::new_remote(StandardId::new(0x1A3).unwrap(), 4).unwrap());
+        let ext_remote = Transmit::new(&Frame::new_remote(extended, 4).unwrap());
+
+        for (input, transmit) in [
+            ("t1232aabb\r", data),
+            ("t1232AABB\r", data),
+            ("t1232aABb\r", data),
+            ("T12abcdef2aabb\r", ext_data),
+            ("T12ABCDEF2AABB\r", ext_data),
+            ("r1a34\r", remote),
+            ("r1A34\r", remote),
+            ("R12abcdef4\r", ext_remote),
+            ("R12ABCDEF4\r", ext_remote),
+        ] {
+            assert_eq!(
+                Transmit::try_parse(input),
+                Ok(("", transmit)),
+                "{:?}",
+                input
+            );
+        }
+
+        // Signs are not hex digits, even where `from_str_radix` accepts them.
+        for input in [
+            "t+231AA\r",
+            "T+2ABCDEF1AA\r",
+            "r+234\r",
+            "R+2ABCDEF4\r",
+            "t123+\r",
+            "t1231+A\r",
+            "t1232AA+B\r",
+        ] {
+            assert!(Transmit::try_parse(input).is_err(), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn frame_new_with_dlc() {
+        let id = StandardId::new(0x123).unwrap();
+        let word = [1, 2, 3, 4, 5, 6, 7, 8];
+
+        let frame = Frame::new_with_dlc(id, &word, 3).unwrap();
+        assert_eq!(frame.dlc(), 3);
+        assert_eq!(frame.data(), &[1, 2, 3]);
+        assert!(frame.is_data_frame());
+        assert_eq!(Frame::new_with_dlc(id, &word, 8).unwrap().data(), &word);
+        assert!(Frame::new_with_dlc(id, &word, 9).is_none());
+    }
+
+    #[test]
+    fn frame_rtr() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+        let frame = Frame::new_rtr(id, 4).unwrap();
+        assert_eq!(frame, Frame::new_remote(id, 4).unwrap());
+        assert!(frame.is_rtr());
+
+        assert!(!Frame::new(id, &[]).unwrap().is_rtr());
+        assert!(Frame::new_rtr(id, 9).is_none());
+    }
+
+    #[test]
+    fn frame_new_remote_from_data_frame() {
+        let id = StandardId::new(0x123).unwrap();
+        let frame = Frame::new(id, &[1, 2, 3]).unwrap();
+
+        let remote = frame.new_remote_from_data_frame().unwrap();
+        assert_eq!(remote, Frame::new_remote(id, 3).unwrap());
+        assert_eq!(remote.new_remote_from_data_frame(), None);
+    }
+
+    #[test]
+    fn frame_id_type() {
+        let frame = Frame::new(Id::Standard(StandardId::new(0x123).unwrap()), &[]).unwrap();
+        assert_eq!(frame.id_type(), IdType::Standard11);
+        assert_eq!(format!("{}", frame.id_type()), "11-bit");
+
+        let frame = Frame::new(Id::Extended(ExtendedId::new(0x123).unwrap()), &[]).unwrap();
+        assert_eq!(frame.id_type(), IdType::Extended29);
+        assert_eq!(format!("{}", frame.id_type()), "29-bit");
+    }
+
+    #[test]
+    fn frame_bit_width() {
+        let frame = Frame::new(StandardId::new(0x123).unwrap(), &[]).unwrap();
+        assert!(frame.is_11bit());
+        assert!(!frame.is_29bit());
+
+        let frame = Frame::new(ExtendedId::new(0x123).unwrap(), &[]).unwrap();
+        assert!(!frame.is_11bit());
+        assert!(frame.is_29bit());
+    }
+
+    #[test]
+    fn frame_is_identical_to() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+        let frame = Frame::new(id, &[1, 2]).unwrap();
+        assert!(frame.is_identical_to(&Frame::new(id, &[1, 2]).unwrap()));
+        assert!(!frame.is_identical_to(&Frame::new(id, &[1, 3]).unwrap()));
+        assert!(!frame.is_identical_to(&Frame::new_remote(id, 2).unwrap()));
+    }
+
+    #[test]
+    fn frame_eq_ignores_padding() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        fn hash(frame: &Frame) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            frame.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let id = StandardId::new(0x123).unwrap();
+        let padded = Frame::new_with_dlc(id, &[1, 2, 3, 4, 5, 6, 7, 8], 2).unwrap();
+        let clean = Frame::new(id, &[1, 2]).unwrap();
+        assert_eq!(padded, clean);
+        assert_eq!(hash(&padded), hash(&clean));
+        assert!(!padded.is_identical_to(&clean));
+
+        let longer = Frame::new_with_dlc(id, &[1, 2, 3, 4, 5, 6, 7, 8], 3).unwrap();
+        assert_ne!(padded, longer);
+        assert_ne!(clean, Frame::new(id, &[1, 2, 0]).unwrap());
+        assert_ne!(clean, Frame::new_remote(id, 2).unwrap());
+    }
+
+    #[test]
+    fn frame_with_id() {
+        let frame = Frame::new(StandardId::new(0x123).unwrap(), &[1, 2]).unwrap();
+        let moved = frame.with_id(ExtendedId::new(0x456).unwrap());
+
+        assert_eq!(moved.id(), Id::Extended(ExtendedId::new(0x456).unwrap()));
+        assert_eq!(moved.data(), frame.data());
+        assert!(moved.is_data_frame());
+    }
+
+    #[test]
+    fn frame_id_range() {
+        let frame = Frame::new(StandardId::new(0x123).unwrap(), &[]).unwrap();
+        assert!(frame.id_is_in_range(0x100, 0x1FF));
+        assert!(frame.id_is_in_range(0x123, 0x123));
+        assert!(!frame.id_is_in_range(0x124, 0x7FF));
+        assert!(!frame.id_is_in_range(0x200, 0x100));
+
+        assert!(frame.id_is_exactly(0x123));
+        assert!(!frame.id_is_exactly(0x124));
+
+        let frame = Frame::new(ExtendedId::new(0x18DAF110).unwrap(), &[]).unwrap();
+        assert!(frame.id_is_in_range(0x18DA0000, 0x18DAFFFF));
+        assert!(frame.id_is_exactly(0x18DAF110));
+    }
+
+    #[test]
+    fn frame_octal() {
+        let frame = Frame::new(StandardId::new(0x123).unwrap(), &[0, 8, 0o377]).unwrap();
+        assert_eq!(format!("{:o}", frame), "000 010 377");
+
+        let frame = Frame::new(StandardId::new(0x123).unwrap(), &[]).unwrap();
+        assert_eq!(format!("{:o}", frame), "");
+
+        let frame = Frame::new_remote(StandardId::new(0x123).unwrap(), 4).unwrap();
+        assert_eq!(format!("{:o}", frame), "");
+    }
+
+    #[test]
+    fn frame_new_from_str_radix() {
+        assert_eq!(
+            Frame::new_from_str_radix("7E0", "0201"),
+            Frame::new(StandardId::new(0x7E0).unwrap(), &[0x02, 0x01])
+        );
+        assert_eq!(
+            Frame::new_from_str_radix("18DAF110", ""),
+            Frame::new(ExtendedId::new(0x18DAF110).unwrap(), &[])
+        );
+
+        for (id, data) in [
+            ("7E", "00"),
+            ("800", "00"),
+            ("7E0", "0"),
+            ("7E0", "0G"),
+            ("7E0", "+1"),
+            ("7E0", "000000000000000000"),
+            ("2FFFFFFF", ""),
+        ] {
+            assert_eq!(Frame::new_from_str_radix(id, data), None);
+        }
+    }
+
+    #[test]
+    fn frame_into_extended() {
+        let frame = Frame::new(StandardId::new(0x7FF).unwrap(), &[1, 2]).unwrap();
+        assert_eq!(
+            frame.into_extended(),
+            Frame::new(ExtendedId::new(0x7FF).unwrap(), &[1, 2]).unwrap()
+        );
+
+        let frame = Frame::new_remote(ExtendedId::new(0x12ABCDEF).unwrap(), 3).unwrap();
+        assert_eq!(frame.into_extended(), frame);
+    }
+
+    #[test]
+    fn frame_data_compare() {
+        let frame = Frame::new(StandardId::new(0x123).unwrap(), &[1, 2, 3]).unwrap();
+
+        assert!(frame.data_equals(&[1, 2, 3]));
+        assert!(!frame.data_equals(&[1, 2, 3, 0]));
+        assert!(frame.data_starts_with(&[1, 2]));
+        assert!(!frame.data_starts_with(&[2]));
+        assert!(frame.data_ends_with(&[2, 3]));
+        assert!(!frame.data_ends_with(&[2]));
+    }
+
+    #[test]
+    fn frame_data_iter() {
+        let frame = Frame::new(StandardId::new(0x123).unwrap(), &[1, 2, 3]).unwrap();
+        assert!(frame.data_iter().eq(&[1, 2, 3]));
+
+        let mut sum = 0;
+        for byte in &frame {
+            sum += *byte;
+        }
+        assert_eq!(sum, 6);
+
+        let frame = Frame::new_remote(StandardId::new(0x123).unwrap(), 3).unwrap();
+        assert_eq!(frame.data_iter().count(), frame.data().len());
+    }
+
+    #[test]
+    fn frame_data_as_ascii() {
+        let id = StandardId::new(0x123).unwrap();
+        assert_eq!(
+            Frame::new(id, b"hello").unwrap().data_as_ascii(),
+            Some("hello")
+        );
+        assert_eq!(Frame::new(id, &[]).unwrap().data_as_ascii(), Some(""));
+        assert_eq!(
+            Frame::new(id, "é".as_bytes()).unwrap().data_as_ascii(),
+            None
+        );
+        assert_eq!(Frame::new(id, &[0xFF]).unwrap().data_as_ascii(), None);
+    }
+
+    #[test]
+    fn frame_u128_round_trip() {
+        let frames = [
+            Frame::new(StandardId::ZERO, &[]).unwrap(),
+            Frame::new(StandardId::MAX, &[0x11, 0x22, 0x33]).unwrap(),
+            Frame::new(ExtendedId::MAX, &[0xFF; 8]).unwrap(),
+            Frame::new_remote(StandardId::new(0x123).unwrap(), 8).unwrap(),
+            Frame::new_remote(ExtendedId::new(0x12ABCDEF).unwrap(), 0).unwrap(),
+        ];
+
+        for frame in frames {
+            let unpacked = Frame::from_u128(frame.to_u128()).unwrap();
+            assert!(unpacked.is_identical_to(&frame));
+        }
+
+        let frame = Frame::new(StandardId::new(0x123).unwrap(), &[0xAA, 0x55]).unwrap();
+        assert_eq!(
+            frame.to_u128(),
+            0x123 << 97 | 2 << 88 | 0xAA55_0000_0000_0000
+        );
+    }
+
+    #[test]
+    fn frame_from_u128_invalid() {
+        // Standard ID above 0x7FF.
+        assert!(Frame::from_u128(0x800 << 97).is_none());
+        // Extended ID above 0x1FFFFFFF.
+        assert!(Frame::from_u128(0x2000_0000 << 97 | 1 << 96).is_none());
+        // DLC above 8.
+        assert!(Frame::from_u128(9 << 88).is_none());
+    }
+
+    #[test]
+    fn transmit_eq() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+        let a = Transmit::new(&Frame::new(id, &[1, 2]).unwrap());
+        let b = Transmit::new(&Frame::new(id, &[1, 2]).unwrap());
+        assert_eq!(a, b);
+
+        let c = Transmit::new(&Frame::new(id, &[1, 3]).unwrap());
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn transmit_hash() {
+        use std::collections::HashSet;
+
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+        let mut set = HashSet::new();
+        assert!(set.insert(Transmit::new(&Frame::new(id, &[1, 2]).unwrap())));
+        assert!(!set.insert(Transmit::new(&Frame::new(id, &[1, 2]).unwrap())));
+        assert!(set.insert(Transmit::new(&Frame::new_remote(id, 2).unwrap())));
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn transmit_id_matches() {
+        let id = StandardId::new(0x123).unwrap();
+        let transmit = Transmit::new(&Frame::new(id, &[]).unwrap());
+
+        assert!(transmit.id_matches(id));
+        assert!(!transmit.id_matches(ExtendedId::new(0x123).unwrap()));
+        assert!(transmit.raw_id_matches(0x123));
+        assert!(!transmit.raw_id_matches(0x124));
+    }
+
+    #[test]
+    fn transmit_is_response_to() {
+        let frame = |id: Id| Transmit::new(&Frame::new(id, &[]).unwrap());
+        let request = frame(StandardId::new(0x7E0).unwrap().into());
+        let response = frame(StandardId::new(0x7E8).unwrap().into());
+
+        assert!(response.is_response_to(&request));
+        assert!(!request.is_response_to(&response));
+        assert!(!request.is_response_to(&request));
+        assert!(!frame(StandardId::new(0x7E9).unwrap().into()).is_response_to(&request));
+
+        let extended = frame(ExtendedId::new(0x7E8).unwrap().into());
+        assert!(!extended.is_response_to(&request));
+        assert!(!response.is_response_to(&frame(ExtendedId::new(0x7E0).unwrap().into())));
+    }
+
+    #[test]
+    fn transmit_loopback_echo() {
+        let frame = Frame::new(StandardId::new(0x123).unwrap(), &[1, 2]).unwrap();
+        let transmit = Transmit::new(&frame);
+        assert_eq!(transmit.new_loopback_echo(), transmit);
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn transmit_new_batch() {
+        let frames = [
+            Frame::new(StandardId::new(0x1).unwrap(), &[1]).unwrap(),
+            Frame::new(StandardId::new(0x2).unwrap(), &[2]).unwrap(),
+            Frame::new(StandardId::new(0x3).unwrap(), &[3]).unwrap(),
+        ];
+
+        let batch = Transmit::new_batch::<4>(&frames);
+        assert_eq!(batch.len(), 3);
+        assert_eq!(batch[2], Transmit::new(&frames[2]));
+
+        let batch = Transmit::new_batch::<2>(&frames);
+        assert_eq!(batch.len(), 2);
+        assert_eq!(batch[1], Transmit::new(&frames[1]));
+    }
+
+    #[test]
+    fn transmit_batch_encode() {
+        let frames = [
+            Frame::new(StandardId::new(0x123).unwrap(), &[0xAA]).unwrap(),
+            Frame::new(ExtendedId::MAX, &[0xFF; 8]).unwrap(),
+        ];
+
+        let encoded = Transmit::batch_encode::<2, 28>(&frames);
+        assert_eq!(&encoded[0][..8], b"t1231AA\r");
+        assert_eq!(encoded[0][8..], [0; 20]);
+        assert_eq!(&encoded[1][..27], b"T1FFFFFFF8FFFFFFFFFFFFFFFF\r");
+        assert_eq!(encoded[1][27], 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn transmit_batch_encode_short_buffer() {
+        let frames = [Frame::new(StandardId::ZERO, &[]).unwrap()];
+        Transmit::batch_encode::<1, 26>(&frames);
+    }
+
+    #[test]
+    fn transmit_data_slice_exact() {
+        let frame = Frame::new(
+            Id::Standard(StandardId::new(0x456).unwrap()),
+            &[0x11, 0x22, 0x33],
+        )
+        .unwrap();
+        let transmit = Transmit::new(&frame);
+
+        assert_eq!(transmit.data_slice_exact::<0>(), Some(&[]));
+        assert_eq!(transmit.data_slice_exact::<2>(), Some(&[0x11, 0x22]));
+        assert_eq!(transmit.data_slice_exact::<3>(), Some(&[0x11, 0x22, 0x33]));
+        assert_eq!(transmit.data_slice_exact::<4>(), None);
+    }
+
+    #[test]
+    fn transmit_from_hex_parts() {
+        assert_eq!(
+            Transmit::try_from_hex_parts("123", "aa55"),
+            Ok(Transmit::new(
+                &Frame::new(StandardId::new(0x123).unwrap(), &[0xAA, 0x55]).unwrap()
+            ))
+        );
+        assert_eq!(
+            Transmit::try_from_hex_parts("12ABCDEF", ""),
+            Ok(Transmit::new(
+                &Frame::new(ExtendedId::new(0x12ABCDEF).unwrap(), &[]).unwrap()
+            ))
+        );
+
+        assert_eq!(
+            Transmit::try_from_hex_parts("12G", ""),
+            Err(ParseError::InvalidHex)
+        );
+        assert_eq!(
+            Transmit::try_from_hex_parts("+12", ""),
+            Err(ParseError::InvalidHex)
+        );
+        for id in ["", "1234", "123456789"] {
+            assert_eq!(
+                Transmit::try_from_hex_parts(id, ""),
+                Err(ParseError::InvalidIdLength),
+                "{:?}",
+                id
+            );
+        }
+        assert_eq!(
+            Transmit::try_from_hex_parts("12G4", ""),
+            Err(ParseError::InvalidIdLength)
+        );
+        assert_eq!(
+            Transmit::try_from_hex_parts("800", ""),
+            Err(ParseError::InvalidId)
+        );
+        assert_eq!(
+            Transmit::try_from_hex_parts("20000000", ""),
+            Err(ParseError::InvalidId)
+        );
+        assert_eq!(
+            Transmit::try_from_hex_parts("123", "AAB"),
+            Err(ParseError::InvalidDataLength)
+        );
+        assert_eq!(
+            Transmit::try_from_hex_parts("123", "001122334455667788"),
+            Err(ParseError::InvalidDataLength)
+        );
+    }
+
+    #[test]
+    fn format_command() {
+        let cmd = Command::Open(Open::new());
+        assert_eq!(format!("{}", cmd), "O\r");
 
         let cmd = Command::Setup(Setup::new(Bitrate::Rate100kbit));
         assert_eq!(format!("{}", cmd), "S3\r");
     }
 
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn format_frame_id() {
+        let id = Id::Standard(StandardId::new(0x12).unwrap());
+        assert_eq!(frame_id_as_string(id), "012");
+
+        let id = Id::Extended(ExtendedId::new(0x1FFFFFFF).unwrap());
+        assert_eq!(frame_id_as_string(id), "1FFFFFFF");
+    }
+
+    #[test]
+    fn command_encoding_bytes() {
+        let cmd = Command::Setup(Setup::new(Bitrate::Rate100kbit));
+        assert!(cmd.encoding_bytes().eq(*b"S3\r"));
+
+        let frame = Frame::new(ExtendedId::new(0x12ABCDEF).unwrap(), &[0xFF; 8]).unwrap();
+        let cmd = Command::Transmit(Transmit::new(&frame));
+        let bytes = cmd.encoding_bytes();
+        assert_eq!(bytes.len(), 27);
+        assert!(bytes.eq(format!("{}", cmd).bytes()));
+    }
+
+    #[test]
+    fn command_kind() {
+        let cmd = Command::Setup(Setup::new(Bitrate::Rate100kbit));
+        assert_eq!(cmd.kind(), CommandKind::Setup);
+        assert_eq!(format!("{}", cmd.kind()), "Setup");
+
+        let frame = Frame::new(Id::Standard(StandardId::new(0x123).unwrap()), &[]).unwrap();
+        let cmd = Command::Transmit(Transmit::new(&frame));
+        assert_eq!(cmd.kind(), CommandKind::Transmit);
+        assert_eq!(format!("{}", cmd.kind()), "Transmit");
+    }
+
+    #[test]
+    fn default_command() {
+        assert_eq!(Command::default(), Command::Close(Close::new()));
+        assert_eq!(format!("{}", Command::default()), "C\r");
+    }
+
+    #[test]
+    fn command_max_len() {
+        let standard = StandardId::MAX;
+        let extended = ExtendedId::MAX;
+        let commands = [
+            Command::Setup(Setup::new(Bitrate::Rate1000kbit)),
+            Command::SetupBtr(SetupBtr::new(0xFF, 0xFF)),
+            Command::SetTimestampMode(SetTimestampMode::new(TimestampMode::On)),
+            Command::Open(Open::new()),
+            Command::Close(Close::new()),
+            Command::ListenOnly(OpenListenOnly::new()),
+            Command::StatusFlags(StatusFlagsResponse::new(0xFF)),
+            Command::Version(VersionResponse::new(0xFF, 0xFF)),
+            Command::SerialNumber(SerialNumberResponse::new(0xFFFF)),
+            Command::SetUartBaud(SetUartBaud::new(UartBaudRate::Rate921600)),
+            Command::SetAcceptanceCode(SetAcceptanceCode::new(u32::MAX)),
+            Command::SetAcceptanceMask(SetAcceptanceMask::new(u32::MAX)),
+        ];
+        let frames = [
+            Frame::new(standard, &[0; 8]).unwrap(),
+            Frame::new(extended, &[0; 8]).unwrap(),
+            Frame::new_remote(standard, 8).unwrap(),
+            Frame::new_remote(extended, 8).unwrap(),
+        ];
+
+        for cmd in commands {
+            let encoded = format!("{}", cmd);
+            assert_eq!(
+                slcan_command_max_len(encoded.as_bytes()[0]),
+                Some(encoded.len())
+            );
+        }
+
+        for frame in frames {
+            let encoded = format!("{}", Transmit::new(&frame));
+            let timestamped = format!("{}", FrameTimestamp::new(frame, 0xFFFF));
+            assert_eq!(
+                slcan_command_max_len(encoded.as_bytes()[0]),
+                Some(timestamped.len())
+            );
+            assert!(encoded.len() <= MAX_FRAME_ENCODED_LEN);
+            assert!(timestamped.len() <= MAX_COMMAND_LEN);
+        }
+
+        assert_eq!(slcan_command_max_len(b'x'), None);
+        assert_eq!(slcan_command_max_len(b'\r'), None);
+    }
+
+    #[test]
+    fn encode_to_slice() {
+        let frame = Frame::new(StandardId::new(0x123).unwrap(), &[0xAA, 0x55]).unwrap();
+        let commands = [
+            Command::Setup(Setup::new(Bitrate::Rate500kbit)),
+            Command::Open(Open::new()),
+            Command::Close(Close::new()),
+            Command::Transmit(Transmit::new(&frame)),
+            Command::Transmit(Transmit::new(&Frame::new_remote(frame.id(), 4).unwrap())),
+            Command::Transmit(Transmit::zero_dlc()),
+            Command::SetupBtr(SetupBtr::new(0x03, 0x1C)),
+            Command::ReadStatusFlags(ReadStatusFlags::new()),
+        ];
+
+        for command in commands {
+            let mut buf = [0; MAX_COMMAND_LEN];
+            let len = command.encode_to_slice(&mut buf).unwrap();
+            assert_eq!(len, command.encoded_len());
+            assert_eq!(&buf[..len], format!("{}", command).as_bytes());
+        }
+
+        let frame = Frame::new(ExtendedId::MAX, &[0xFF; 8]).unwrap();
+        let transmit = Transmit::new(&frame);
+        assert_eq!(transmit.encoded_len(), MAX_FRAME_ENCODED_LEN);
+
+        let mut buf = [0; MAX_FRAME_ENCODED_LEN];
+        assert_eq!(
+            transmit.encode_to_slice(&mut buf),
+            Ok(MAX_FRAME_ENCODED_LEN)
+        );
+        assert_eq!(&buf, b"T1FFFFFFF8FFFFFFFFFFFFFFFF\r");
+
+        let mut buf = [0; MAX_FRAME_ENCODED_LEN - 1];
+        assert_eq!(
+            transmit.encode_to_slice(&mut buf),
+            Err(EncodeError::BufferTooSmall {
+                needed: MAX_FRAME_ENCODED_LEN
+            })
+        );
+        assert_eq!(buf, [0; MAX_FRAME_ENCODED_LEN - 1]);
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn defmt_format() {
+        fn assert_format<T: defmt::Format>() {}
+
+        assert_format::<Bitrate>();
+        assert_format::<Setup>();
+        assert_format::<SetupBtr>();
+        assert_format::<Open>();
+        assert_format::<OpenListenOnly>();
+        assert_format::<Close>();
+        assert_format::<TimestampMode>();
+        assert_format::<SetTimestampMode>();
+        assert_format::<UartBaudRate>();
+        assert_format::<SetUartBaud>();
+        assert_format::<SetAcceptanceCode>();
+        assert_format::<SetAcceptanceMask>();
+        assert_format::<Transmit>();
+        assert_format::<Command>();
+        assert_format::<CommandKind>();
+        assert_format::<Frame>();
+        assert_format::<IdType>();
+        assert_format::<FrameTimestamp>();
+        assert_format::<SlcanTimestamp>();
+        assert_format::<Response>();
+        assert_format::<StatusFlagsResponse>();
+        assert_format::<VersionResponse>();
+        assert_format::<SerialNumberResponse>();
+        assert_format::<SlcanError>();
+        assert_format::<EncodeError>();
+        assert_format::<ParseError>();
+        assert_format::<FeedResult>();
+        assert_format::<CommandParser<32>>();
+        assert_format::<SlcanParser>();
+        assert_format::<SequencerState>();
+        assert_format::<SlcanCommandSequencer>();
+        assert_format::<EncodingBytes>();
+        assert_format::<SlcanHexDump>();
+        assert_format::<lexer::Token>();
+        assert_format::<lexer::LexError>();
+        assert_format::<lexer::SlcanLexer>();
+        assert_format::<filter::DualFilter>();
+        assert_format::<filter::FrameFilter>();
+        assert_format::<signal::Intel>();
+        assert_format::<signal::Motorola>();
+        assert_format::<timing::CanTiming>();
+        assert_format::<SlcanDevice<device::Open>>();
+        #[cfg(feature = "ffi")]
+        {
+            assert_format::<ffi::FfiFrame>();
+            assert_format::<ffi::FfiFramePacked>();
+        }
+        #[cfg(feature = "heapless")]
+        assert_format::<CommandBatch<4>>();
+        #[cfg(feature = "metrics")]
+        assert_format::<metrics::SlcanPacketLoss>();
+        #[cfg(feature = "can-fd")]
+        {
+            assert_format::<CanFdFrame>();
+            assert_format::<TransmitFd>();
+            assert_format::<DlcCode>();
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let frames = [
+            Frame::new(StandardId::new(0x123).unwrap(), &[0xAA, 0x55]).unwrap(),
+            Frame::new(ExtendedId::MAX, &[1, 2, 3, 4, 5, 6, 7, 8]).unwrap(),
+            Frame::new(StandardId::ZERO, &[]).unwrap(),
+            Frame::new_remote(StandardId::new(0x7FF).unwrap(), 4).unwrap(),
+            Frame::new_remote(ExtendedId::new(0x12ABCDEF).unwrap(), 0).unwrap(),
+        ];
+
+        for frame in frames {
+            let transmit = Transmit::new(&frame);
+            let json = serde_json::to_string(&transmit).unwrap();
+            assert_eq!(serde_json::from_str::<Transmit>(&json).unwrap(), transmit);
+        }
+
+        let frame = Frame::new_with_dlc(StandardId::new(0x123).unwrap(), &[1; 8], 2).unwrap();
+        assert_eq!(
+            serde_json::to_string(&frame).unwrap(),
+            r#"{"id":291,"extended":false,"remote":false,"dlc":2,"data":[1,1]}"#
+        );
+
+        for json in [
+            r#"{"id":2048,"extended":false,"remote":false,"dlc":0,"data":[]}"#,
+            r#"{"id":1,"extended":false,"remote":false,"dlc":9,"data":[1,2,3,4,5,6,7,8,9]}"#,
+            r#"{"id":1,"extended":false,"remote":false,"dlc":2,"data":[1]}"#,
+            r#"{"id":1,"extended":false,"remote":true,"dlc":1,"data":[1]}"#,
+            r#"{"id":1,"extended":false,"remote":true,"dlc":9,"data":[]}"#,
+        ] {
+            assert!(serde_json::from_str::<Frame>(json).is_err());
+        }
+
+        let err = serde_json::from_str::<Frame>(
+            r#"{"id":1,"extended":false,"remote":true,"dlc":9,"data":[]}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().starts_with("dlc greater than 8"), "{}", err);
+
+        let setup = Setup::new(Bitrate::Rate500kbit);
+        let json = serde_json::to_string(&setup).unwrap();
+        assert_eq!(json, r#"{"bitrate":"Rate500kbit"}"#);
+        assert_eq!(serde_json::from_str::<Setup>(&json).unwrap(), setup);
+        assert_eq!(serde_json::from_str::<Open>("{}").unwrap(), Open::new());
+        assert_eq!(serde_json::from_str::<Close>("{}").unwrap(), Close::new());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn error_context() {
+        let error = SlcanError::InvalidCommandForState
+            .context("opening channel")
+            .context("starting logger");
+
+        assert_eq!(
+            format!("{}", error),
+            "starting logger: opening channel: Command not valid in current adapter state"
+        );
+        assert_eq!(
+            error,
+            SlcanError::WithContext {
+                inner: alloc::boxed::Box::new(SlcanError::WithContext {
+                    inner: alloc::boxed::Box::new(SlcanError::InvalidCommandForState),
+                    context: "opening channel",
+                }),
+                context: "starting logger",
+            }
+        );
+    }
+
+    #[test]
+    fn format_errors() {
+        assert_eq!(
+            format!("{}", SlcanError::Format),
+            "Failed to format slcan command"
+        );
+        assert_eq!(
+            format!("{}", SlcanError::InvalidCommandForState),
+            "Command not valid in current adapter state"
+        );
+        assert_eq!(
+            format!("{}", EncodeError::BufferTooSmall { needed: 27 }),
+            "Buffer too small for encoded command (27 bytes needed)"
+        );
+    }
+
+    #[test]
+    fn command_round_trip() {
+        let frame = Frame::new(StandardId::new(0x123).unwrap(), &[0xAA, 0x55]).unwrap();
+        let commands = [
+            Command::Setup(Setup::new(Bitrate::Rate500kbit)),
+            Command::SetupBtr(SetupBtr::new(0x03, 0x1C)),
+            Command::SetTimestampMode(SetTimestampMode::new(TimestampMode::Off)),
+            Command::Open(Open::new()),
+            Command::ListenOnly(OpenListenOnly::new()),
+            Command::Close(Close::new()),
+            Command::Transmit(Transmit::new(&frame)),
+            Command::TimestampedFrame(FrameTimestamp::new(frame, 0xEA60)),
+            Command::ReadStatusFlags(ReadStatusFlags::new()),
+            Command::StatusFlags(StatusFlagsResponse::new(0x24)),
+            Command::GetVersion(GetVersion::new()),
+            Command::Version(VersionResponse::new(1, 2)),
+            Command::GetSerialNumber(GetSerialNumber::new()),
+            Command::SerialNumber(SerialNumberResponse::new(0xA1B2)),
+            Command::SetUartBaud(SetUartBaud::new(UartBaudRate::Rate115200)),
+            Command::SetAcceptanceCode(SetAcceptanceCode::new(0x1FFFFFFF)),
+            Command::SetAcceptanceMask(SetAcceptanceMask::new(0)),
+        ];
+
+        for cmd in commands {
+            let encoded = format!("{}", cmd);
+            assert_eq!(Command::try_parse(&encoded), Ok(("", cmd)));
+        }
+    }
+
+    #[test]
+    fn command_conversions() {
+        let setup = Setup::new(Bitrate::Rate500kbit);
+        let cmd = Command::from(setup);
+        assert_eq!(cmd, Command::Setup(setup));
+        assert_eq!(Setup::try_from(cmd), Ok(setup));
+        assert_eq!(Open::try_from(cmd), Err(cmd));
+
+        let frame = Frame::new(StandardId::new(0x123).unwrap(), &[0xAA]).unwrap();
+        let cmd: Command = Transmit::new(&frame).into();
+        assert_eq!(cmd.kind(), CommandKind::Transmit);
+        assert_eq!(Transmit::try_from(cmd), Ok(Transmit::new(&frame)));
+
+        let cmd: Command = Close::new().into();
+        assert_eq!(Close::try_from(cmd), Ok(Close::new()));
+        assert_eq!(Transmit::try_from(cmd), Err(cmd));
+
+        let cmd: Command = Open::new().into();
+        assert_eq!(Open::try_from(cmd), Ok(Open::new()));
+    }
+
     #[test]
     fn parse_command() {
         let cmd = Command::try_parse("O\r");
         assert_eq!(cmd, Ok(("", Command::Open(Open::new()))));
+
+        let cmd = Command::try_parse("L\r");
+        assert_eq!(cmd, Ok(("", Command::ListenOnly(OpenListenOnly::new()))));
+        assert_eq!(cmd.unwrap().1.kind(), CommandKind::ListenOnly);
+
+        let cmd = Command::try_parse("s031C\r");
+        assert_eq!(cmd, Ok(("", Command::SetupBtr(SetupBtr::new(0x03, 0x1C)))));
+
+        let cmd = Command::try_parse("t1232AA55EA60\r");
+        assert_eq!(cmd.unwrap().1.kind(), CommandKind::TimestampedFrame);
+        let cmd = Command::try_parse("t1232AA55\r");
+        assert_eq!(cmd.unwrap().1.kind(), CommandKind::Transmit);
+
+        let cmd = Command::try_parse("F\r");
+        assert_eq!(
+            cmd,
+            Ok(("", Command::ReadStatusFlags(ReadStatusFlags::new())))
+        );
+        let cmd = Command::try_parse("F24\r");
+        assert_eq!(
+            cmd,
+            Ok(("", Command::StatusFlags(StatusFlagsResponse::new(0x24))))
+        );
+        assert_eq!(cmd.unwrap().1.kind(), CommandKind::StatusFlags);
+
+        let cmd = Command::try_parse("V\r");
+        assert_eq!(cmd, Ok(("", Command::GetVersion(GetVersion::new()))));
+        let cmd = Command::try_parse("V0102\r");
+        assert_eq!(cmd, Ok(("", Command::Version(VersionResponse::new(1, 2)))));
+        assert_eq!(cmd.unwrap().1.kind(), CommandKind::Version);
+
+        let cmd = Command::try_parse("N\r");
+        assert_eq!(
+            cmd,
+            Ok(("", Command::GetSerialNumber(GetSerialNumber::new())))
+        );
+        let cmd = Command::try_parse("N0000\r");
+        assert_eq!(
+            cmd,
+            Ok(("", Command::SerialNumber(SerialNumberResponse::new(0))))
+        );
+        assert_eq!(cmd.unwrap().1.kind(), CommandKind::SerialNumber);
+
+        let cmd = Command::try_parse("Z1\r");
+        assert_eq!(
+            cmd,
+            Ok((
+                "",
+                Command::SetTimestampMode(SetTimestampMode::new(TimestampMode::On))
+            ))
+        );
+        assert_eq!(cmd.unwrap().1.kind(), CommandKind::SetTimestampMode);
     }
 }
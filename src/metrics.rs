@@ -0,0 +1,131 @@
+//! Link quality metrics.
+
+/// Packet loss tracking based on 8-bit sequence numbers.
+///
+/// Some application layers place an incrementing, wrapping sequence number in
+/// each frame. Gaps in the sequence are counted as lost packets.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SlcanPacketLoss {
+    /// Packets expected so far, including lost packets.
+    pub total: u32,
+    /// Packets missing from the sequence.
+    pub lost: u32,
+    last: Option<u8>,
+}
+
+impl SlcanPacketLoss {
+    pub fn new() -> Self {
+        Self {
+            total: 0,
+            lost: 0,
+            last: None,
+        }
+    }
+
+    /// Record a received sequence number.
+    ///
+    /// A repeat of the last sequence number is a duplicate and is ignored. A
+    /// gap of more than 127 is a backward jump, from reordering or a sender
+    /// restart, so nothing is counted as lost and tracking continues from
+    /// `sequence`.
+    pub fn record(&mut self, sequence: u8) {
+        if let Some(last) = self.last {
+            let gap = match sequence.wrapping_sub(last) {
+                0 => return,
+                step @ 1..=128 => u32::from(step - 1),
+                _ => 0,
+            };
+            self.lost = self.lost.saturating_add(gap);
+            self.total = self.total.saturating_add(gap);
+        }
+
+        self.total = self.total.saturating_add(1);
+        self.last = Some(sequence);
+    }
+
+    /// Record a received frame, using its last data byte as the sequence
+    /// number. Frames without data are ignored.
+    pub fn record_frame(&mut self, frame: &impl embedded_can::Frame) {
+        if let Some(sequence) = frame.data().last() {
+            self.record(*sequence);
+        }
+    }
+
+    /// Fraction of packets lost, from `0.0` to `1.0`.
+    pub fn lost_fraction(&self) -> f32 {
+        if self.total == 0 {
+            return 0.0;
+        }
+
+        self.lost as f32 / self.total as f32
+    }
+
+    /// Check the lost fraction does not exceed `threshold`.
+    pub fn is_healthy(&self, threshold: f32) -> bool {
+        self.lost_fraction() <= threshold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_loss() {
+        let mut loss = SlcanPacketLoss::new();
+        for sequence in 0..=255 {
+            loss.record(sequence);
+        }
+        loss.record(0);
+
+        assert_eq!(loss.total, 257);
+        assert_eq!(loss.lost, 0);
+        assert_eq!(loss.lost_fraction(), 0.0);
+        assert!(loss.is_healthy(0.0));
+    }
+
+    #[test]
+    fn gaps() {
+        let mut loss = SlcanPacketLoss::new();
+        loss.record(254);
+        loss.record(1); // 255 and 0 lost across the wrap
+        loss.record(2);
+        loss.record(4); // 3 lost
+
+        assert_eq!(loss.total, 7);
+        assert_eq!(loss.lost, 3);
+        assert!(loss.is_healthy(0.5));
+        assert!(!loss.is_healthy(0.25));
+    }
+
+    #[test]
+    fn duplicates() {
+        let mut loss = SlcanPacketLoss::new();
+        loss.record(7);
+        loss.record(7);
+        loss.record(8);
+        loss.record(8);
+
+        assert_eq!(loss.total, 2);
+        assert_eq!(loss.lost, 0);
+    }
+
+    #[test]
+    fn backward_jumps() {
+        let mut loss = SlcanPacketLoss::new();
+        loss.record(100);
+        loss.record(101);
+        loss.record(0); // sender restart
+        loss.record(1);
+        loss.record(129); // 127 lost, the largest gap counted
+        loss.record(2); // a gap of 128, so a jump back rather than lost
+
+        assert_eq!(loss.total, 133);
+        assert_eq!(loss.lost, 127);
+
+        loss.record(3);
+        assert_eq!(loss.total, 134);
+        assert_eq!(loss.lost, 127);
+    }
+}
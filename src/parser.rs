@@ -0,0 +1,342 @@
+//! Parsing commands from slcan byte streams and strings.
+
+use crate::{Command, ParseError, TimestampMode, Transmit, MAX_COMMAND_LEN};
+
+/// Result of feeding bytes to a [`CommandParser`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FeedResult {
+    /// A complete command was parsed.
+    Ready(Command),
+    /// More bytes are needed to complete the next command.
+    Incomplete,
+    /// The next command was malformed, or the buffer overflowed.
+    Error(ParseError),
+}
+
+/// Parser that buffers up to `N` bytes of a stream and yields complete
+/// commands as their `\r` terminator arrives.
+///
+/// Each call to [`CommandParser::feed`] returns at most one command and keeps
+/// any bytes after it buffered. When a chunk may hold several commands, call
+/// [`CommandParser::poll`] until it returns [`FeedResult::Incomplete`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CommandParser<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+    overflowed: bool,
+}
+
+impl<const N: usize> CommandParser<N> {
+    pub fn new() -> Self {
+        Self {
+            buf: [0; N],
+            len: 0,
+            overflowed: false,
+        }
+    }
+
+    /// Append `data` to the buffer and return the next command, as
+    /// [`CommandParser::poll`].
+    ///
+    /// Bytes that do not fit in the buffer are dropped.
+    pub fn feed(&mut self, data: &[u8]) -> FeedResult {
+        let fits = data.len().min(N - self.len);
+        self.buf[self.len..self.len + fits].copy_from_slice(&data[..fits]);
+        self.len += fits;
+
+        if fits < data.len() {
+            self.overflowed = true;
+        }
+
+        self.poll()
+    }
+
+    /// Return the next buffered command without adding any bytes.
+    ///
+    /// A malformed command is discarded up to and including its `\r`. Once
+    /// every complete command has been returned, [`ParseError::BufferOverflow`]
+    /// is returned if the buffer is full or bytes were dropped, until
+    /// [`CommandParser::reset`] resynchronises with the stream.
+    pub fn poll(&mut self) -> FeedResult {
+        let Some(end) = self.buf[..self.len].iter().position(|&b| b == b'\r') else {
+            if self.overflowed || self.len == N {
+                return FeedResult::Error(ParseError::BufferOverflow);
+            }
+            return FeedResult::Incomplete;
+        };
+        let line = &self.buf[..=end];
+
+        let result = match parse_line(line) {
+            Ok(command) => FeedResult::Ready(command),
+            Err(err) => FeedResult::Error(err),
+        };
+
+        self.buf.copy_within(end + 1..self.len, 0);
+        self.len -= end + 1;
+
+        result
+    }
+
+    /// Discard all buffered bytes and clear any overflow.
+    pub fn reset(&mut self) {
+        self.len = 0;
+        self.overflowed = false;
+    }
+}
+
+impl<const N: usize> Default for CommandParser<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse a single `\r` terminated command that makes up all of `line`.
+fn parse_line(line: &[u8]) -> Result<Command, ParseError> {
+    match Command::try_parse_bytes(line) {
+        Ok((&[], command)) => Ok(command),
+        _ if !line.ends_with(b"\r") => Err(ParseError::Incomplete),
+        _ => Err(match line[0] {
+            b't' | b'T' | b'r' | b'R' => match Transmit::try_parse_slice(line) {
+                Err(err) => err,
+                Ok(_) => ParseError::InvalidCommand,
+            },
+            byte if crate::slcan_command_max_len(byte).is_some() => ParseError::InvalidCommand,
+            _ => ParseError::UnknownCommand,
+        }),
+    }
+}
+
+/// Parser for single commands with configurable strictness.
+///
+/// The default parser accepts exactly one `\r` terminated command, with or
+/// without a timestamp.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SlcanParser {
+    /// Ignore surrounding whitespace, such as a `\n` after the `\r`, and
+    /// accept a missing `\r` terminator.
+    pub lenient: bool,
+    /// Require received frames to carry a timestamp ([`TimestampMode::On`])
+    /// or to not carry one ([`TimestampMode::Off`]). `None` accepts both.
+    pub timestamp_mode: Option<TimestampMode>,
+}
+
+impl SlcanParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse `input` as exactly one command.
+    pub fn parse(&self, input: &str) -> Result<Command, ParseError> {
+        let command = if self.lenient {
+            let trimmed = input.trim_matches(|c: char| c.is_ascii_whitespace());
+            if trimmed.len() >= MAX_COMMAND_LEN {
+                return Err(ParseError::InvalidCommand);
+            }
+
+            let mut buf = [0; MAX_COMMAND_LEN];
+            buf[..trimmed.len()].copy_from_slice(trimmed.as_bytes());
+            buf[trimmed.len()] = b'\r';
+
+            parse_line(&buf[..=trimmed.len()])?
+        } else {
+            parse_line(input.as_bytes())?
+        };
+
+        match (self.timestamp_mode, &command) {
+            (Some(TimestampMode::On), Command::Transmit(_))
+            | (Some(TimestampMode::Off), Command::TimestampedFrame(_)) => {
+                Err(ParseError::InvalidTimestampLength)
+            }
+            _ => Ok(command),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bitrate, Close, Frame, Open, Setup};
+    use embedded_can::{Frame as _, StandardId};
+
+    #[test]
+    fn byte_at_a_time() {
+        let stream = b"S6\rO\rt1232AA55\rT12ABCDEF0\rr1238\rC\r";
+        let mut parser = CommandParser::<32>::new();
+        let mut commands = Vec::new();
+
+        for byte in stream {
+            match parser.feed(&[*byte]) {
+                FeedResult::Ready(command) => commands.push(command),
+                FeedResult::Incomplete => {}
+                FeedResult::Error(err) => panic!("unexpected error {:?}", err),
+            }
+        }
+
+        let id = StandardId::new(0x123).unwrap();
+        assert_eq!(commands.len(), 6);
+        assert_eq!(
+            commands[0],
+            Command::Setup(Setup::new(Bitrate::Rate500kbit))
+        );
+        assert_eq!(commands[1], Command::Open(Open::new()));
+        assert_eq!(
+            commands[2],
+            Command::Transmit(Transmit::new(&Frame::new(id, &[0xAA, 0x55]).unwrap()))
+        );
+        assert!(matches!(commands[3], Command::Transmit(_)));
+        assert_eq!(
+            commands[4],
+            Command::Transmit(Transmit::new(&Frame::new_remote(id, 8).unwrap()))
+        );
+        assert_eq!(commands[5], Command::Close(Close::new()));
+    }
+
+    #[test]
+    fn several_commands_in_one_chunk() {
+        let mut parser = CommandParser::<16>::new();
+
+        assert_eq!(
+            parser.feed(b"O\rC\rS"),
+            FeedResult::Ready(Command::Open(Open::new()))
+        );
+        assert_eq!(
+            parser.poll(),
+            FeedResult::Ready(Command::Close(Close::new()))
+        );
+        assert_eq!(parser.poll(), FeedResult::Incomplete);
+        assert_eq!(
+            parser.feed(b"8\r"),
+            FeedResult::Ready(Command::Setup(Setup::new(Bitrate::Rate1000kbit)))
+        );
+        assert_eq!(parser.poll(), FeedResult::Incomplete);
+    }
+
+    #[test]
+    fn malformed_commands() {
+        let mut parser = CommandParser::<16>::new();
+
+        assert_eq!(
+            parser.feed(b"x\r"),
+            FeedResult::Error(ParseError::UnknownCommand)
+        );
+        assert_eq!(
+            parser.feed(b"S9\r"),
+            FeedResult::Error(ParseError::InvalidCommand)
+        );
+        assert_eq!(
+            parser.feed(b"t1G31AA\r"),
+            FeedResult::Error(ParseError::InvalidHex)
+        );
+        assert_eq!(
+            parser.feed(b"\r"),
+            FeedResult::Error(ParseError::UnknownCommand)
+        );
+        assert_eq!(
+            parser.feed(b"O\r"),
+            FeedResult::Ready(Command::Open(Open::new()))
+        );
+    }
+
+    #[test]
+    fn slcan_parser_default() {
+        let parser = SlcanParser::new();
+
+        assert_eq!(parser.parse("O\r"), Ok(Command::Open(Open::new())));
+        assert!(matches!(
+            parser.parse("t1230EA60\r"),
+            Ok(Command::TimestampedFrame(_))
+        ));
+        assert_eq!(parser.parse("O"), Err(ParseError::Incomplete));
+        assert_eq!(parser.parse(""), Err(ParseError::Incomplete));
+        assert_eq!(parser.parse("O\r\n"), Err(ParseError::Incomplete));
+        assert_eq!(parser.parse("O\rC\r"), Err(ParseError::InvalidCommand));
+        assert_eq!(parser.parse("x\r"), Err(ParseError::UnknownCommand));
+        assert_eq!(parser.parse("t1G31AA\r"), Err(ParseError::InvalidHex));
+    }
+
+    #[test]
+    fn slcan_parser_lenient() {
+        let parser = SlcanParser {
+            lenient: true,
+            ..SlcanParser::new()
+        };
+
+        assert_eq!(parser.parse("O\r\n"), Ok(Command::Open(Open::new())));
+        assert_eq!(parser.parse(" O"), Ok(Command::Open(Open::new())));
+        assert_eq!(parser.parse("O"), Ok(Command::Open(Open::new())));
+        assert_eq!(parser.parse("\r\n"), Err(ParseError::UnknownCommand));
+        assert!(matches!(
+            parser.parse("T12ABCDEF8AABBCCDDEEFF0011EA60\r\n"),
+            Ok(Command::TimestampedFrame(_))
+        ));
+        assert_eq!(
+            parser.parse("T12ABCDEF8AABBCCDDEEFF0011EA600"),
+            Err(ParseError::InvalidCommand)
+        );
+    }
+
+    #[test]
+    fn slcan_parser_timestamp_mode() {
+        let on = SlcanParser {
+            timestamp_mode: Some(TimestampMode::On),
+            ..SlcanParser::new()
+        };
+        let off = SlcanParser {
+            timestamp_mode: Some(TimestampMode::Off),
+            ..SlcanParser::new()
+        };
+
+        assert!(on.parse("t1230EA60\r").is_ok());
+        assert_eq!(on.parse("t1230\r"), Err(ParseError::InvalidTimestampLength));
+        assert!(off.parse("t1230\r").is_ok());
+        assert_eq!(
+            off.parse("t1230EA60\r"),
+            Err(ParseError::InvalidTimestampLength)
+        );
+        assert_eq!(on.parse("O\r"), Ok(Command::Open(Open::new())));
+    }
+
+    #[test]
+    fn overflow_and_reset() {
+        let mut parser = CommandParser::<4>::new();
+
+        // Exactly full with no terminator.
+        assert_eq!(
+            parser.feed(b"t123"),
+            FeedResult::Error(ParseError::BufferOverflow)
+        );
+        assert_eq!(
+            parser.feed(b"O\r"),
+            FeedResult::Error(ParseError::BufferOverflow)
+        );
+        assert_eq!(parser.poll(), FeedResult::Error(ParseError::BufferOverflow));
+
+        parser.reset();
+        assert_eq!(
+            parser.feed(b"O\r"),
+            FeedResult::Ready(Command::Open(Open::new()))
+        );
+        assert_eq!(parser.poll(), FeedResult::Incomplete);
+    }
+
+    #[test]
+    fn overflow_after_buffered_command() {
+        let mut parser = CommandParser::<4>::new();
+
+        // The complete command is returned before the overflow is reported.
+        assert_eq!(
+            parser.feed(b"O\rt1230\r"),
+            FeedResult::Ready(Command::Open(Open::new()))
+        );
+        assert_eq!(parser.poll(), FeedResult::Error(ParseError::BufferOverflow));
+
+        parser.reset();
+        assert_eq!(
+            parser.feed(b"C\r"),
+            FeedResult::Ready(Command::Close(Close::new()))
+        );
+    }
+}
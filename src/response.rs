@@ -0,0 +1,523 @@
+//! Responses sent from the adapter to the host.
+
+use crate::{hex_number, EncodingBytes, FrameTimestamp, Transmit};
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_until},
+    combinator::{all_consuming, map, recognize},
+    error::{Error, ErrorKind},
+    sequence::{pair, tuple},
+    Err, IResult,
+};
+
+/// Messages sent from the adapter to the host.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Response {
+    /// Command accepted (`\r`).
+    Ack,
+    /// Command rejected (`\x07`).
+    Nack,
+    /// Frame received from the bus.
+    Frame(Transmit),
+    /// Frame received from the bus while timestamps are enabled.
+    TimestampedFrame(FrameTimestamp),
+    /// Reply to [`ReadStatusFlags`].
+    StatusFlags(StatusFlagsResponse),
+    /// Reply to [`GetVersion`].
+    Version(VersionResponse),
+    /// Reply to [`GetSerialNumber`].
+    SerialNumber(SerialNumberResponse),
+}
+
+/// Former name of [`Response`].
+#[deprecated(note = "renamed to `Response`")]
+pub type SlcanResponse = Response;
+
+impl Response {
+    /// Try parsing a [`Response`] from a string.
+    ///
+    /// The parser is chosen by the first character. Each message consumes its
+    /// own `\r` terminator, so a `\r` left at the start of the remaining input
+    /// is always a separate [`Response::Ack`].
+    pub fn try_parse(input: &str) -> IResult<&str, Self> {
+        match input.as_bytes().first() {
+            Some(b'\r') => map(tag("\r"), |_| Response::Ack)(input),
+            Some(b'\x07') => map(tag("\x07"), |_| Response::Nack)(input),
+            // Frames without a timestamp are tried first, the timestamp is
+            // only recognised by what follows the data.
+            Some(b't' | b'T' | b'r' | b'R') => alt((
+                map(Transmit::try_parse, Response::Frame),
+                map(FrameTimestamp::try_parse, Response::TimestampedFrame),
+            ))(input),
+            Some(b'F') => map(StatusFlagsResponse::try_parse, Response::StatusFlags)(input),
+            Some(b'V') => map(VersionResponse::try_parse, Response::Version)(input),
+            Some(b'N') => map(SerialNumberResponse::try_parse, Response::SerialNumber)(input),
+            _ => Err(Err::Error(Error::new(input, ErrorKind::Char))),
+        }
+    }
+
+    /// Try parsing any known response from bytes read from the adapter.
+    ///
+    /// Single byte acknowledgements are checked first. Anything else must be a
+    /// complete line ending in `\r`.
+    pub fn try_parse_any(input: &[u8]) -> IResult<&[u8], Response> {
+        alt((
+            map(tag("\r"), |_| Response::Ack),
+            map(tag("\x07"), |_| Response::Nack),
+            response_line,
+        ))(input)
+    }
+}
+
+/// Try parsing a [`Response`] from bytes read from the adapter.
+///
+/// Same as [`Response::try_parse_any`].
+pub fn parse_slcan_response(input: &[u8]) -> IResult<&[u8], Response> {
+    Response::try_parse_any(input)
+}
+
+fn response_line(input: &[u8]) -> IResult<&[u8], Response> {
+    let (rest, line) = recognize(pair(take_until("\r"), tag("\r")))(input)?;
+    let line =
+        core::str::from_utf8(line).map_err(|_| Err::Error(Error::new(input, ErrorKind::Char)))?;
+
+    let (_, response) =
+        all_consuming(Response::try_parse)(line).map_err(|e| e.map_input(|_| input))?;
+
+    Ok((rest, response))
+}
+
+/// Read status flags command.
+///
+/// The adapter replies with a [`StatusFlagsResponse`].
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ReadStatusFlags {}
+
+impl ReadStatusFlags {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Iterate over the encoded bytes of this command.
+    pub fn encoding_bytes(&self) -> EncodingBytes {
+        EncodingBytes::new(self)
+    }
+
+    /// Try parsing a [`ReadStatusFlags`] command from a string.
+    pub fn try_parse(input: &str) -> IResult<&str, Self> {
+        let (input, _) = tag("F\r")(input)?;
+        Ok((input, Self {}))
+    }
+}
+
+impl core::fmt::Display for ReadStatusFlags {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "F\r")
+    }
+}
+
+/// Status flags reported in reply to [`ReadStatusFlags`].
+///
+/// The flags follow the SJA1000 interrupt register layout used by LAWICEL
+/// adapters. Bit 4 is unused.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct StatusFlagsResponse {
+    raw: u8,
+}
+
+impl StatusFlagsResponse {
+    pub fn new(raw: u8) -> Self {
+        Self { raw }
+    }
+
+    /// The raw flags byte.
+    pub fn raw(&self) -> u8 {
+        self.raw
+    }
+
+    fn bit(&self, bit: u8) -> bool {
+        self.raw & (1 << bit) != 0
+    }
+
+    /// The receive FIFO is full and frames may have been lost.
+    pub fn rx_overrun(&self) -> bool {
+        self.bit(0)
+    }
+
+    /// The transmit FIFO is full.
+    pub fn tx_overrun(&self) -> bool {
+        self.bit(1)
+    }
+
+    /// An error counter has reached the warning limit.
+    pub fn error_warning(&self) -> bool {
+        self.bit(2)
+    }
+
+    /// The CAN controller receive buffer overran.
+    pub fn data_overrun(&self) -> bool {
+        self.bit(3)
+    }
+
+    /// The controller is error passive.
+    pub fn tx_error_passive(&self) -> bool {
+        self.bit(5)
+    }
+
+    /// The controller lost arbitration while transmitting.
+    pub fn arbitration_lost(&self) -> bool {
+        self.bit(6)
+    }
+
+    /// A bus error was detected.
+    pub fn bus_error(&self) -> bool {
+        self.bit(7)
+    }
+
+    /// Iterate over the encoded bytes of this response.
+    pub fn encoding_bytes(&self) -> EncodingBytes {
+        EncodingBytes::new(self)
+    }
+
+    /// Try parsing a [`StatusFlagsResponse`] from a string.
+    pub fn try_parse(input: &str) -> IResult<&str, Self> {
+        let (input, (_, raw, _)) = tuple((tag("F"), hex_number(2), tag("\r")))(input)?;
+        Ok((input, Self::new(raw as u8)))
+    }
+}
+
+impl core::fmt::Display for StatusFlagsResponse {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "F{:02X}\r", self.raw)
+    }
+}
+
+/// Get version command.
+///
+/// The adapter replies with a [`VersionResponse`].
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GetVersion {}
+
+impl GetVersion {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Iterate over the encoded bytes of this command.
+    pub fn encoding_bytes(&self) -> EncodingBytes {
+        EncodingBytes::new(self)
+    }
+
+    /// Try parsing a [`GetVersion`] command from a string.
+    pub fn try_parse(input: &str) -> IResult<&str, Self> {
+        let (input, _) = tag("V\r")(input)?;
+        Ok((input, Self {}))
+    }
+}
+
+impl core::fmt::Display for GetVersion {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "V\r")
+    }
+}
+
+/// Hardware and software versions reported in reply to [`GetVersion`].
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct VersionResponse {
+    pub hw_version: u8,
+    pub sw_version: u8,
+}
+
+impl VersionResponse {
+    pub fn new(hw_version: u8, sw_version: u8) -> Self {
+        Self {
+            hw_version,
+            sw_version,
+        }
+    }
+
+    /// Iterate over the encoded bytes of this response.
+    pub fn encoding_bytes(&self) -> EncodingBytes {
+        EncodingBytes::new(self)
+    }
+
+    /// Try parsing a [`VersionResponse`] from a string.
+    pub fn try_parse(input: &str) -> IResult<&str, Self> {
+        let (input, (_, hw_version, sw_version, _)) =
+            tuple((tag("V"), hex_number(2), hex_number(2), tag("\r")))(input)?;
+
+        Ok((input, Self::new(hw_version as u8, sw_version as u8)))
+    }
+}
+
+impl core::fmt::Display for VersionResponse {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "V{:02X}{:02X}\r", self.hw_version, self.sw_version)
+    }
+}
+
+/// Get serial number command.
+///
+/// The adapter replies with a [`SerialNumberResponse`].
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GetSerialNumber {}
+
+impl GetSerialNumber {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Iterate over the encoded bytes of this command.
+    pub fn encoding_bytes(&self) -> EncodingBytes {
+        EncodingBytes::new(self)
+    }
+
+    /// Try parsing a [`GetSerialNumber`] command from a string.
+    pub fn try_parse(input: &str) -> IResult<&str, Self> {
+        let (input, _) = tag("N\r")(input)?;
+        Ok((input, Self {}))
+    }
+}
+
+impl core::fmt::Display for GetSerialNumber {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "N\r")
+    }
+}
+
+/// Serial number reported in reply to [`GetSerialNumber`].
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SerialNumberResponse {
+    pub sn: u16,
+}
+
+impl SerialNumberResponse {
+    pub fn new(sn: u16) -> Self {
+        Self { sn }
+    }
+
+    /// Iterate over the encoded bytes of this response.
+    pub fn encoding_bytes(&self) -> EncodingBytes {
+        EncodingBytes::new(self)
+    }
+
+    /// Try parsing a [`SerialNumberResponse`] from a string.
+    ///
+    /// The serial number must be exactly 4 hex digits.
+    pub fn try_parse(input: &str) -> IResult<&str, Self> {
+        let (input, (_, sn, _)) = tuple((tag("N"), hex_number(4), tag("\r")))(input)?;
+        Ok((input, Self::new(sn as u16)))
+    }
+}
+
+impl core::fmt::Display for SerialNumberResponse {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "N{:04X}\r", self.sn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Frame;
+    use embedded_can::{Frame as _, Id, StandardId};
+
+    #[test]
+    fn parse_ack_nack() {
+        assert_eq!(parse_slcan_response(b"\r"), Ok((&b""[..], Response::Ack)));
+        assert_eq!(
+            parse_slcan_response(b"\x07\r"),
+            Ok((&b"\r"[..], Response::Nack))
+        );
+    }
+
+    #[test]
+    fn read_status_flags() {
+        assert_eq!(format!("{}", ReadStatusFlags::new()), "F\r");
+        assert_eq!(
+            ReadStatusFlags::try_parse("F\r"),
+            Ok(("", ReadStatusFlags::new()))
+        );
+        assert!(ReadStatusFlags::try_parse("F00\r").is_err());
+    }
+
+    #[test]
+    fn status_flags_response() {
+        let (_, none) = StatusFlagsResponse::try_parse("F00\r").unwrap();
+        assert_eq!(none.raw(), 0);
+        assert!(!none.rx_overrun());
+        assert!(!none.bus_error());
+
+        let (_, all) = StatusFlagsResponse::try_parse("FFf\r").unwrap();
+        assert_eq!(all.raw(), 0xFF);
+        assert!(all.rx_overrun());
+        assert!(all.tx_overrun());
+        assert!(all.error_warning());
+        assert!(all.data_overrun());
+        assert!(all.tx_error_passive());
+        assert!(all.arbitration_lost());
+        assert!(all.bus_error());
+        assert_eq!(format!("{}", all), "FFF\r");
+
+        let flags = StatusFlagsResponse::new(0x24);
+        assert!(flags.error_warning());
+        assert!(flags.tx_error_passive());
+        assert!(!flags.data_overrun());
+        assert!(!flags.bus_error());
+
+        assert!(StatusFlagsResponse::try_parse("FFff\r").is_err());
+        assert!(StatusFlagsResponse::try_parse("F0\r").is_err());
+        assert!(StatusFlagsResponse::try_parse("F0G\r").is_err());
+        assert!(StatusFlagsResponse::try_parse("F\r").is_err());
+    }
+
+    #[test]
+    fn get_version() {
+        assert_eq!(format!("{}", GetVersion::new()), "V\r");
+        assert_eq!(GetVersion::try_parse("V\r"), Ok(("", GetVersion::new())));
+        assert!(GetVersion::try_parse("v\r").is_err());
+    }
+
+    #[test]
+    fn version_response() {
+        assert_eq!(
+            VersionResponse::try_parse("V0102\r"),
+            Ok(("", VersionResponse::new(1, 2)))
+        );
+        assert_eq!(
+            VersionResponse::try_parse("VFFFF\r"),
+            Ok(("", VersionResponse::new(0xFF, 0xFF)))
+        );
+        assert_eq!(format!("{}", VersionResponse::new(1, 0xA2)), "V01A2\r");
+
+        assert!(VersionResponse::try_parse("V010\r").is_err());
+        assert!(VersionResponse::try_parse("V01020\r").is_err());
+        assert!(VersionResponse::try_parse("V01G2\r").is_err());
+    }
+
+    #[test]
+    fn get_serial_number() {
+        assert_eq!(format!("{}", GetSerialNumber::new()), "N\r");
+        assert_eq!(
+            GetSerialNumber::try_parse("N\r"),
+            Ok(("", GetSerialNumber::new()))
+        );
+    }
+
+    #[test]
+    fn serial_number_response() {
+        assert_eq!(
+            SerialNumberResponse::try_parse("N0000\r"),
+            Ok(("", SerialNumberResponse::new(0)))
+        );
+        assert_eq!(
+            SerialNumberResponse::try_parse("NA1b2\r"),
+            Ok(("", SerialNumberResponse::new(0xA1B2)))
+        );
+
+        for sn in [0, 0x0123, 0xFFFF] {
+            let response = SerialNumberResponse::new(sn);
+            let encoded = format!("{}", response);
+            assert_eq!(
+                SerialNumberResponse::try_parse(&encoded),
+                Ok(("", response))
+            );
+        }
+
+        assert!(SerialNumberResponse::try_parse("N123\r").is_err());
+        assert!(SerialNumberResponse::try_parse("N12345\r").is_err());
+        assert!(SerialNumberResponse::try_parse("N12G4\r").is_err());
+    }
+
+    #[test]
+    fn parse_received_frame() {
+        let frame = Frame::new(Id::Standard(StandardId::new(0x123).unwrap()), &[0xAA]).unwrap();
+
+        assert_eq!(
+            parse_slcan_response(b"t1231AA\r\r"),
+            Ok((&b"\r"[..], Response::Frame(Transmit::new(&frame))))
+        );
+
+        assert!(parse_slcan_response(b"t1231AA").is_err());
+        assert!(parse_slcan_response(b"t1231AA\x07\r").is_err());
+        assert!(parse_slcan_response(b"x\r").is_err());
+    }
+
+    #[test]
+    fn try_parse_any() {
+        let frame = Frame::new(Id::Standard(StandardId::new(0x123).unwrap()), &[0xAA]).unwrap();
+
+        for (input, response) in [
+            (&b"\r"[..], Response::Ack),
+            (b"\x07", Response::Nack),
+            (b"t1231AA\r", Response::Frame(Transmit::new(&frame))),
+            (
+                b"t1231AAEA60\r",
+                Response::TimestampedFrame(FrameTimestamp::new(frame, 60_000)),
+            ),
+            (
+                b"F24\r",
+                Response::StatusFlags(StatusFlagsResponse::new(0x24)),
+            ),
+            (b"V0102\r", Response::Version(VersionResponse::new(1, 2))),
+            (
+                b"N0000\r",
+                Response::SerialNumber(SerialNumberResponse::new(0)),
+            ),
+        ] {
+            assert_eq!(Response::try_parse_any(input), Ok((&b""[..], response)));
+        }
+
+        assert!(Response::try_parse_any(b"F\r").is_err());
+        assert!(Response::try_parse_any(b"V01\r").is_err());
+        assert!(Response::try_parse_any(b"N123\r").is_err());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn slcan_response_alias() {
+        assert_eq!(
+            SlcanResponse::try_parse_any(b"\x07"),
+            Ok((&b""[..], SlcanResponse::Nack))
+        );
+    }
+
+    #[test]
+    fn parse_mixed_stream() {
+        let frame = Frame::new(Id::Standard(StandardId::new(0x123).unwrap()), &[0xAA]).unwrap();
+        let mut input = "\rt1231AA\r\r\x07F24\rt1230\rt1231AAEA60\r\r";
+        let mut responses = Vec::new();
+
+        while !input.is_empty() {
+            let (rest, response) = Response::try_parse(input).unwrap();
+            responses.push(response);
+            input = rest;
+        }
+
+        let empty = Frame::new(Id::Standard(StandardId::new(0x123).unwrap()), &[]).unwrap();
+        assert_eq!(
+            responses,
+            [
+                Response::Ack,
+                Response::Frame(Transmit::new(&frame)),
+                Response::Ack,
+                Response::Nack,
+                Response::StatusFlags(StatusFlagsResponse::new(0x24)),
+                Response::Frame(Transmit::new(&empty)),
+                Response::TimestampedFrame(FrameTimestamp::new(frame, 60_000)),
+                Response::Ack,
+            ]
+        );
+
+        assert!(Response::try_parse("").is_err());
+        assert!(Response::try_parse("x\r").is_err());
+        assert!(Response::try_parse("t1231AA").is_err());
+    }
+}
@@ -0,0 +1,163 @@
+//! Command ordering checks.
+
+use crate::{Command, CommandKind, SlcanError};
+
+/// Adapter states, as described on [`Command`].
+#[derive(Debug, Default, PartialEq, Eq, Hash, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SequencerState {
+    /// No bitrate has been set up yet.
+    #[default]
+    Unconfigured,
+    /// A bitrate is set up and the port is closed.
+    Configured,
+    /// The port is open.
+    Open,
+    /// The port is open in listen-only mode.
+    ListenOnly,
+}
+
+/// Tracks the adapter state and rejects commands it would not accept.
+///
+/// A [`crate::Transmit`] is only accepted once the port has been set up and
+/// opened, for example.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SlcanCommandSequencer {
+    state: SequencerState,
+}
+
+impl SlcanCommandSequencer {
+    pub fn new() -> Self {
+        Self {
+            state: SequencerState::Unconfigured,
+        }
+    }
+
+    /// The current adapter state.
+    pub fn state(&self) -> SequencerState {
+        self.state
+    }
+
+    /// The state after `command`, or `None` if `command` is not valid in the
+    /// current state.
+    fn next_state(&self, command: &Command) -> Option<SequencerState> {
+        use SequencerState::*;
+
+        match (self.state, command.kind()) {
+            (_, CommandKind::Close) => Some(Unconfigured),
+            (_, CommandKind::GetVersion | CommandKind::GetSerialNumber) => Some(self.state),
+            (Unconfigured | Configured, CommandKind::Setup | CommandKind::SetupBtr) => {
+                Some(Configured)
+            }
+            (
+                Unconfigured | Configured,
+                CommandKind::SetTimestampMode
+                | CommandKind::SetUartBaud
+                | CommandKind::SetAcceptanceCode
+                | CommandKind::SetAcceptanceMask,
+            ) => Some(self.state),
+            (Configured, CommandKind::Open) => Some(Open),
+            (Configured, CommandKind::ListenOnly) => Some(ListenOnly),
+            (Open, CommandKind::Transmit) => Some(Open),
+            (Open | ListenOnly, CommandKind::ReadStatusFlags) => Some(self.state),
+            _ => None,
+        }
+    }
+
+    /// Check if `command` is valid in the current state.
+    pub fn is_allowed(&self, command: &Command) -> bool {
+        self.next_state(command).is_some()
+    }
+
+    /// Check `command` against the current state and advance to the next
+    /// state.
+    ///
+    /// The state is left unchanged if `command` is rejected.
+    pub fn advance(&mut self, command: &Command) -> Result<(), SlcanError> {
+        self.state = self
+            .next_state(command)
+            .ok_or(SlcanError::InvalidCommandForState)?;
+
+        Ok(())
+    }
+
+    /// Return to the unconfigured state, for example after an adapter reset.
+    pub fn reset(&mut self) {
+        self.state = SequencerState::Unconfigured;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Bitrate, Close, Frame, GetVersion, Open, OpenListenOnly, ReadStatusFlags, Setup, SetupBtr,
+        Transmit,
+    };
+    use embedded_can::{Frame as _, StandardId};
+
+    fn transmit() -> Command {
+        let frame = Frame::new(StandardId::new(0x123).unwrap(), &[0xAA]).unwrap();
+        Command::Transmit(Transmit::new(&frame))
+    }
+
+    #[test]
+    fn valid_sequence() {
+        let mut sequencer = SlcanCommandSequencer::new();
+
+        for (command, state) in [
+            (
+                Command::Setup(Setup::new(Bitrate::Rate500kbit)),
+                SequencerState::Configured,
+            ),
+            (Command::Open(Open::new()), SequencerState::Open),
+            (transmit(), SequencerState::Open),
+            (
+                Command::ReadStatusFlags(ReadStatusFlags::new()),
+                SequencerState::Open,
+            ),
+            (Command::Close(Close::new()), SequencerState::Unconfigured),
+            (
+                Command::SetupBtr(SetupBtr::new(0x03, 0x1C)),
+                SequencerState::Configured,
+            ),
+            (
+                Command::ListenOnly(OpenListenOnly::new()),
+                SequencerState::ListenOnly,
+            ),
+            (Command::Close(Close::new()), SequencerState::Unconfigured),
+        ] {
+            assert_eq!(sequencer.advance(&command), Ok(()));
+            assert_eq!(sequencer.state(), state);
+        }
+    }
+
+    #[test]
+    fn invalid_order() {
+        let mut sequencer = SlcanCommandSequencer::new();
+
+        assert!(!sequencer.is_allowed(&transmit()));
+        assert!(sequencer.is_allowed(&Command::GetVersion(GetVersion::new())));
+        assert!(!sequencer.is_allowed(&Command::ReadStatusFlags(ReadStatusFlags::new())));
+        assert_eq!(
+            sequencer.advance(&Command::Open(Open::new())),
+            Err(SlcanError::InvalidCommandForState)
+        );
+        assert_eq!(sequencer.state(), SequencerState::Unconfigured);
+
+        sequencer
+            .advance(&Command::Setup(Setup::new(Bitrate::Rate500kbit)))
+            .unwrap();
+        assert!(!sequencer.is_allowed(&transmit()));
+
+        sequencer
+            .advance(&Command::ListenOnly(OpenListenOnly::new()))
+            .unwrap();
+        assert!(!sequencer.is_allowed(&transmit()));
+        assert!(!sequencer.is_allowed(&Command::Setup(Setup::new(Bitrate::Rate250kbit))));
+
+        sequencer.reset();
+        assert_eq!(sequencer.state(), SequencerState::Unconfigured);
+    }
+}
@@ -0,0 +1,184 @@
+//! CAN signal decoding, in the style of DBC signal definitions.
+
+use crate::Frame;
+use embedded_can::Frame as _;
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for u8 {}
+    impl Sealed for u16 {}
+    impl Sealed for u32 {}
+    impl Sealed for u64 {}
+    impl Sealed for super::Intel {}
+    impl Sealed for super::Motorola {}
+}
+
+/// Unsigned integer types a signal can be decoded into.
+pub trait Bitfield: sealed::Sealed + Sized {
+    /// Width of the type in bits.
+    const BITS: u8;
+
+    /// Truncate `value` to this type.
+    fn from_u64(value: u64) -> Self;
+}
+
+impl Bitfield for u8 {
+    const BITS: u8 = u8::BITS as u8;
+
+    fn from_u64(value: u64) -> Self {
+        value as u8
+    }
+}
+
+impl Bitfield for u16 {
+    const BITS: u8 = u16::BITS as u8;
+
+    fn from_u64(value: u64) -> Self {
+        value as u16
+    }
+}
+
+impl Bitfield for u32 {
+    const BITS: u8 = u32::BITS as u8;
+
+    fn from_u64(value: u64) -> Self {
+        value as u32
+    }
+}
+
+impl Bitfield for u64 {
+    const BITS: u8 = u64::BITS as u8;
+
+    fn from_u64(value: u64) -> Self {
+        value
+    }
+}
+
+/// Byte order of a signal.
+pub trait ByteOrder: sealed::Sealed {
+    /// Extract `length` bits starting at `offset` from the 8 data bytes, or
+    /// `None` if the signal runs past the end of the data.
+    ///
+    /// `offset` is at most 63 and `length` is at least 1.
+    fn extract(data: [u8; 8], offset: u8, length: u8) -> Option<u64>;
+}
+
+/// Little endian (Intel) byte order.
+///
+/// `offset` is the bit position of the least significant bit, counting from
+/// bit 0 of byte 0.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Intel;
+
+impl ByteOrder for Intel {
+    fn extract(data: [u8; 8], offset: u8, length: u8) -> Option<u64> {
+        if offset as u16 + length as u16 > 64 {
+            return None;
+        }
+
+        let raw = u64::from_le_bytes(data);
+        Some((raw >> offset) & mask(length))
+    }
+}
+
+/// Big endian (Motorola) byte order.
+///
+/// `offset` is the DBC start bit, the position of the most significant bit
+/// counting from bit 0 of byte 0. The signal continues into the following
+/// bytes.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Motorola;
+
+impl ByteOrder for Motorola {
+    fn extract(data: [u8; 8], offset: u8, length: u8) -> Option<u64> {
+        let raw = u64::from_be_bytes(data);
+        let msb = (7 - offset / 8) * 8 + offset % 8;
+        let lsb = msb.checked_sub(length - 1)?;
+        Some((raw >> lsb) & mask(length))
+    }
+}
+
+fn mask(length: u8) -> u64 {
+    u64::MAX >> (64 - length)
+}
+
+impl Frame {
+    /// Extract the `length` bit signal at `offset` from the frame data.
+    ///
+    /// Data beyond the DLC reads as zero. Returns `None` if `offset` is
+    /// greater than 63, `length` is zero or wider than `T`, or the signal runs
+    /// past the last byte.
+    pub fn bitfield<T: Bitfield, O: ByteOrder>(&self, offset: u8, length: u8) -> Option<T> {
+        if length == 0 || length > T::BITS || offset > 63 {
+            return None;
+        }
+
+        let mut data = [0; 8];
+        if self.is_data_frame() {
+            data[..self.dlc()].copy_from_slice(self.data());
+        }
+
+        O::extract(data, offset, length).map(T::from_u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_can::StandardId;
+
+    fn frame(data: &[u8]) -> Frame {
+        Frame::new(StandardId::new(0x123).unwrap(), data).unwrap()
+    }
+
+    #[test]
+    fn intel() {
+        let frame = frame(&[0x12, 0x34, 0x56, 0x78]);
+
+        assert_eq!(frame.bitfield::<u16, Intel>(0, 16), Some(0x3412));
+        assert_eq!(frame.bitfield::<u8, Intel>(4, 8), Some(0x41));
+        assert_eq!(frame.bitfield::<u8, Intel>(0, 1), Some(0));
+        assert_eq!(frame.bitfield::<u8, Intel>(1, 1), Some(1));
+        assert_eq!(frame.bitfield::<u32, Intel>(0, 32), Some(0x78563412));
+        assert_eq!(frame.bitfield::<u64, Intel>(0, 64), Some(0x78563412));
+        assert_eq!(frame.bitfield::<u8, Intel>(32, 8), Some(0));
+    }
+
+    #[test]
+    fn motorola() {
+        let frame = frame(&[0x12, 0x34, 0x56, 0x78]);
+
+        assert_eq!(frame.bitfield::<u16, Motorola>(7, 16), Some(0x1234));
+        assert_eq!(frame.bitfield::<u8, Motorola>(7, 4), Some(0x1));
+        assert_eq!(frame.bitfield::<u8, Motorola>(3, 8), Some(0x23));
+        assert_eq!(frame.bitfield::<u32, Motorola>(7, 32), Some(0x12345678));
+        assert_eq!(frame.bitfield::<u8, Motorola>(63, 8), Some(0));
+        assert_eq!(frame.bitfield::<u8, Motorola>(56, 1), Some(0));
+    }
+
+    #[test]
+    fn motorola_last_bytes() {
+        let frame = frame(&[0, 0, 0, 0, 0, 0, 0xAB, 0xCD]);
+
+        assert_eq!(frame.bitfield::<u8, Motorola>(63, 8), Some(0xCD));
+        assert_eq!(frame.bitfield::<u16, Motorola>(55, 16), Some(0xABCD));
+        assert_eq!(frame.bitfield::<u8, Motorola>(63, 4), Some(0xC));
+        assert_eq!(frame.bitfield::<u8, Motorola>(59, 4), Some(0xD));
+        assert_eq!(frame.bitfield::<u16, Motorola>(63, 9), None);
+    }
+
+    #[test]
+    fn out_of_range() {
+        let frame = frame(&[0xFF; 8]);
+
+        assert_eq!(frame.bitfield::<u64, Intel>(60, 5), None);
+        assert_eq!(frame.bitfield::<u64, Intel>(255, 8), None);
+        assert_eq!(frame.bitfield::<u8, Motorola>(64, 1), None);
+        assert_eq!(frame.bitfield::<u8, Intel>(0, 9), None);
+        assert_eq!(frame.bitfield::<u8, Intel>(0, 0), None);
+        assert_eq!(frame.bitfield::<u8, Intel>(56, 8), Some(0xFF));
+    }
+}
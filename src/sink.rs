@@ -0,0 +1,66 @@
+//! Sending commands over [`embedded_io::Write`] transports.
+
+use crate::{Bitrate, Command, Frame, Setup, SlcanError, Transmit};
+
+/// Send slcan commands to a byte sink.
+///
+/// Implemented for every [`embedded_io::Write`] type.
+pub trait SlcanSink {
+    /// Send a [`Setup`] command configuring `bitrate`.
+    fn send_setup(&mut self, bitrate: Bitrate) -> Result<(), SlcanError>;
+
+    /// Send a [`Transmit`] command for `frame`.
+    fn send_frame(&mut self, frame: &Frame) -> Result<(), SlcanError>;
+
+    /// Send any [`Command`].
+    fn send_command(&mut self, command: &Command) -> Result<(), SlcanError>;
+}
+
+impl<T: embedded_io::Write> SlcanSink for T {
+    fn send_setup(&mut self, bitrate: Bitrate) -> Result<(), SlcanError> {
+        write!(self, "{}", Setup::new(bitrate))?;
+        Ok(())
+    }
+
+    fn send_frame(&mut self, frame: &Frame) -> Result<(), SlcanError> {
+        write!(self, "{}", Transmit::new(frame))?;
+        Ok(())
+    }
+
+    fn send_command(&mut self, command: &Command) -> Result<(), SlcanError> {
+        write!(self, "{}", command)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Open;
+    use embedded_can::{Frame as _, StandardId};
+
+    #[test]
+    fn send() {
+        let mut buf = [0; 16];
+        let mut sink = &mut buf[..];
+
+        sink.send_setup(Bitrate::Rate500kbit).unwrap();
+        sink.send_command(&Command::Open(Open::new())).unwrap();
+        let frame = Frame::new(StandardId::new(0x123).unwrap(), &[0xAA]).unwrap();
+        sink.send_frame(&frame).unwrap();
+
+        let remaining = sink.len();
+        assert_eq!(&buf[..buf.len() - remaining], b"S6\rO\rt1231AA\r");
+    }
+
+    #[test]
+    fn send_full() {
+        let mut buf = [0; 2];
+        let mut sink = &mut buf[..];
+
+        assert_eq!(
+            sink.send_setup(Bitrate::Rate500kbit),
+            Err(SlcanError::Io(embedded_io::ErrorKind::WriteZero))
+        );
+    }
+}
@@ -0,0 +1,233 @@
+//! Received frame timestamps.
+
+use crate::{hex_number, parse_frame, write_frame, EncodingBytes, Frame, ParseError};
+use embedded_can::Id;
+use nom::{bytes::complete::tag, sequence::tuple, IResult};
+
+/// Millisecond timestamp appended to received frames when timestamps are
+/// enabled.
+///
+/// Timestamps are sent as 4 hex digits and wrap after
+/// [`SlcanTimestamp::wraps_after_ms`] milliseconds.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SlcanTimestamp {
+    pub millis: u16,
+}
+
+impl SlcanTimestamp {
+    pub fn new(millis: u16) -> Self {
+        Self { millis }
+    }
+
+    /// Parse exactly 4 hex digits.
+    pub fn from_hex(s: &str) -> Option<Self> {
+        if s.len() != 4 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let millis = u16::from_str_radix(s, 16).ok()?;
+
+        Some(Self { millis })
+    }
+
+    /// Encode as 4 uppercase hex digits.
+    pub fn to_hex(&self) -> [u8; 4] {
+        const DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+        let mut hex = [0; 4];
+        for (i, digit) in hex.iter_mut().enumerate() {
+            let nibble = (self.millis >> (12 - i * 4)) & 0xF;
+            *digit = DIGITS[nibble as usize];
+        }
+
+        hex
+    }
+
+    /// Milliseconds after which adapters wrap the timestamp back to zero.
+    pub const fn wraps_after_ms() -> u16 {
+        60_000
+    }
+}
+
+impl TryFrom<&str> for SlcanTimestamp {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        if s.len() != 4 {
+            return Err(ParseError::InvalidTimestampLength);
+        }
+
+        Self::from_hex(s).ok_or(ParseError::InvalidHex)
+    }
+}
+
+impl core::str::FromStr for SlcanTimestamp {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+/// Frame received while timestamps are enabled, for example `t1232AA55EA60\r`.
+///
+/// Frames without a timestamp are parsed by [`crate::Transmit::try_parse`].
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FrameTimestamp {
+    pub frame: Frame,
+    /// Millisecond counter, see [`SlcanTimestamp`].
+    pub timestamp: u16,
+}
+
+impl FrameTimestamp {
+    pub fn new(frame: Frame, timestamp: u16) -> Self {
+        Self { frame, timestamp }
+    }
+
+    /// Iterate over the encoded bytes of this frame.
+    pub fn encoding_bytes(&self) -> EncodingBytes {
+        EncodingBytes::new(self)
+    }
+
+    /// Try parsing a [`FrameTimestamp`] from a string.
+    pub fn try_parse(input: &str) -> IResult<&str, Self> {
+        let (input, (frame, timestamp, _)) = tuple((parse_frame, hex_number(4), tag("\r")))(input)?;
+
+        Ok((input, Self::new(frame, timestamp as u16)))
+    }
+}
+
+impl core::fmt::Display for FrameTimestamp {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write_frame(f, &self.frame, false)?;
+        write!(f, "{:04X}\r", self.timestamp)
+    }
+}
+
+impl embedded_can::Frame for FrameTimestamp {
+    fn new(id: impl Into<Id>, data: &[u8]) -> Option<Self> {
+        Some(Self::new(Frame::new(id, data)?, 0))
+    }
+
+    fn new_remote(id: impl Into<Id>, dlc: usize) -> Option<Self> {
+        Some(Self::new(Frame::new_remote(id, dlc)?, 0))
+    }
+
+    fn is_extended(&self) -> bool {
+        self.frame.is_extended()
+    }
+
+    fn is_remote_frame(&self) -> bool {
+        self.frame.is_remote_frame()
+    }
+
+    fn id(&self) -> Id {
+        self.frame.id()
+    }
+
+    fn dlc(&self) -> usize {
+        self.frame.dlc()
+    }
+
+    fn data(&self) -> &[u8] {
+        self.frame.data()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_round_trip() {
+        for (hex, millis) in [
+            ("0000", 0),
+            ("EA60", 60_000),
+            ("FFFF", 0xFFFF),
+            ("0a1B", 0x0A1B),
+        ] {
+            let timestamp = SlcanTimestamp::from_hex(hex).unwrap();
+            assert_eq!(timestamp.millis, millis);
+            assert_eq!(&timestamp.to_hex(), hex.to_ascii_uppercase().as_bytes());
+        }
+    }
+
+    #[test]
+    fn from_hex_invalid() {
+        assert_eq!(SlcanTimestamp::from_hex(""), None);
+        assert_eq!(SlcanTimestamp::from_hex("123"), None);
+        assert_eq!(SlcanTimestamp::from_hex("12345"), None);
+        assert_eq!(SlcanTimestamp::from_hex("+123"), None);
+        assert_eq!(SlcanTimestamp::from_hex("12G4"), None);
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!("EA60".parse(), Ok(SlcanTimestamp::new(60_000)));
+        assert_eq!(SlcanTimestamp::try_from("0001"), Ok(SlcanTimestamp::new(1)));
+        assert_eq!(
+            "123".parse::<SlcanTimestamp>(),
+            Err(ParseError::InvalidTimestampLength)
+        );
+        assert_eq!(
+            "12G4".parse::<SlcanTimestamp>(),
+            Err(ParseError::InvalidHex)
+        );
+    }
+
+    #[test]
+    fn frame_timestamp_parse() {
+        use embedded_can::{ExtendedId, Frame as _, StandardId};
+
+        let standard = StandardId::new(0x123).unwrap();
+        let extended = ExtendedId::new(0x12ABCDEF).unwrap();
+
+        for (input, frame, timestamp) in [
+            (
+                "t1232AA550000\r",
+                Frame::new(standard, &[0xAA, 0x55]).unwrap(),
+                0,
+            ),
+            (
+                "T12ABCDEF1FFEA60\r",
+                Frame::new(extended, &[0xFF]).unwrap(),
+                60_000,
+            ),
+            (
+                "r1234FFFF\r",
+                Frame::new_remote(standard, 4).unwrap(),
+                0xFFFF,
+            ),
+            (
+                "R12ABCDEF0EA60\r",
+                Frame::new_remote(extended, 0).unwrap(),
+                60_000,
+            ),
+        ] {
+            let parsed = FrameTimestamp::new(frame, timestamp);
+            assert_eq!(FrameTimestamp::try_parse(input), Ok(("", parsed)));
+            assert_eq!(format!("{}", parsed), input);
+            assert_eq!(parsed.id(), frame.id());
+            assert_eq!(parsed.data(), frame.data());
+        }
+
+        assert_eq!(
+            FrameTimestamp::try_parse("t1231aaea60\r"),
+            Ok((
+                "",
+                FrameTimestamp::new(Frame::new(standard, &[0xAA]).unwrap(), 60_000)
+            ))
+        );
+
+        assert!(FrameTimestamp::try_parse("t1232AA55\r").is_err());
+        assert!(FrameTimestamp::try_parse("t1232AA55EA6\r").is_err());
+        assert!(FrameTimestamp::try_parse("t1232AA55EA6G\r").is_err());
+    }
+
+    #[test]
+    fn wraps() {
+        assert_eq!(SlcanTimestamp::wraps_after_ms(), 60_000);
+    }
+}
@@ -0,0 +1,267 @@
+//! CAN bit timing.
+
+use crate::{Bitrate, Frame};
+use embedded_can::Frame as _;
+
+/// CAN bit timing parameters.
+///
+/// Segment lengths are in time quanta. The synchronisation segment is always
+/// one time quantum and is not stored.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CanTiming {
+    /// Clock divider producing the time quantum.
+    pub prescaler: u16,
+    /// Propagation segment.
+    pub prop_seg: u8,
+    /// Phase segment 1, before the sample point.
+    pub phase_seg1: u8,
+    /// Phase segment 2, after the sample point.
+    pub phase_seg2: u8,
+    /// Synchronisation jump width.
+    pub sjw: u8,
+}
+
+impl CanTiming {
+    pub const fn new(
+        prescaler: u16,
+        prop_seg: u8,
+        phase_seg1: u8,
+        phase_seg2: u8,
+        sjw: u8,
+    ) -> Self {
+        Self {
+            prescaler,
+            prop_seg,
+            phase_seg1,
+            phase_seg2,
+            sjw,
+        }
+    }
+
+    /// Number of time quanta in one bit.
+    pub const fn time_quanta(&self) -> u32 {
+        1 + self.prop_seg as u32 + self.phase_seg1 as u32 + self.phase_seg2 as u32
+    }
+
+    /// Bitrate produced from a peripheral clock of `clock_hz`.
+    pub const fn nominal_bitrate(&self, clock_hz: u32) -> u32 {
+        if self.prescaler == 0 {
+            return 0;
+        }
+
+        clock_hz / (self.prescaler as u32 * self.time_quanta())
+    }
+
+    /// Sample point as a percentage of the bit time.
+    pub fn sample_point_percent(&self) -> f32 {
+        let sample = self.time_quanta() - self.phase_seg2 as u32;
+        sample as f32 * 100.0 / self.time_quanta() as f32
+    }
+
+    /// Sample point in tenths of a percent of the bit time, rounded down.
+    pub const fn sample_point_permille(&self) -> u16 {
+        let sample = self.time_quanta() - self.phase_seg2 as u32;
+        (sample * 1000 / self.time_quanta()) as u16
+    }
+
+    /// Check the parameters are within the limits of the CAN specification.
+    ///
+    /// A bit must be 8 to 25 time quanta long and the synchronisation jump
+    /// width must be between 1 and 4 and no longer than either phase.
+    pub const fn is_valid(&self) -> bool {
+        let quanta = self.time_quanta();
+
+        self.prescaler > 0
+            && self.phase_seg2 > 0
+            && self.sjw > 0
+            && self.sjw <= 4
+            && self.sjw <= self.phase_seg2
+            && self.sjw as u32 <= self.prop_seg as u32 + self.phase_seg1 as u32
+            && quanta >= 8
+            && quanta <= 25
+    }
+}
+
+/// Estimate the fraction of bus capacity used by `fps` standard data frames
+/// per second with an average DLC of `avg_dlc`.
+///
+/// Each frame is counted as 44 bits of framing overhead (start of frame,
+/// arbitration, control, CRC, acknowledgement, end of frame and interframe
+/// space) plus its data, ignoring bit stuffing. A result above `1.0` means the
+/// bus cannot carry the load.
+pub fn estimate_bus_load(fps: f32, avg_dlc: f32, bitrate: Bitrate) -> f32 {
+    (fps * (44.0 + avg_dlc * 8.0)) / bitrate.as_bps() as f32
+}
+
+impl Frame {
+    /// Estimate how long this frame occupies the bus at `bitrate`, in
+    /// microseconds, rounded up.
+    ///
+    /// The frame is counted as 1 start of frame bit, 11 or 29 identifier bits,
+    /// 3 control bits, its data, 15 CRC bits and 3 end of frame bits, plus 25%
+    /// for bit stuffing. Remote frames carry no data bits.
+    pub fn time_to_transmit_us(&self, bitrate: Bitrate) -> u32 {
+        let id_bits: u32 = if self.is_29bit() { 29 } else { 11 };
+        let data_bits = if self.is_rtr() {
+            0
+        } else {
+            self.dlc() as u32 * 8
+        };
+        let bits = 1 + id_bits + 3 + data_bits + 15 + 3;
+
+        // bits * 1.25 * 1_000_000 / bps, rounded up.
+        (bits * 5 * 1_000_000).div_ceil(bitrate.as_bps() * 4)
+    }
+}
+
+/// Precomputed timings for common peripheral clocks.
+///
+/// Timings aim for a sample point of 87.5% with a synchronisation jump width
+/// of one time quantum.
+pub(crate) fn precomputed(bitrate: Bitrate, clock_hz: u32) -> Option<CanTiming> {
+    let table = match clock_hz {
+        8_000_000 => &CLOCK_8MHZ,
+        16_000_000 => &CLOCK_16MHZ,
+        24_000_000 => &CLOCK_24MHZ,
+        48_000_000 => &CLOCK_48MHZ,
+        _ => return None,
+    };
+
+    Some(table[bitrate as usize])
+}
+
+/// Timings for an 8 MHz clock, indexed by [`Bitrate`].
+const CLOCK_8MHZ: [CanTiming; 9] = [
+    CanTiming::new(50, 5, 8, 2, 1), // 10 kbit/s
+    CanTiming::new(25, 5, 8, 2, 1), // 20 kbit/s
+    CanTiming::new(10, 5, 8, 2, 1), // 50 kbit/s
+    CanTiming::new(5, 5, 8, 2, 1),  // 100 kbit/s
+    CanTiming::new(4, 5, 8, 2, 1),  // 125 kbit/s
+    CanTiming::new(2, 5, 8, 2, 1),  // 250 kbit/s
+    CanTiming::new(1, 5, 8, 2, 1),  // 500 kbit/s
+    CanTiming::new(1, 1, 7, 1, 1),  // 800 kbit/s
+    CanTiming::new(1, 1, 5, 1, 1),  // 1000 kbit/s
+];
+
+/// Timings for a 16 MHz clock, indexed by [`Bitrate`].
+const CLOCK_16MHZ: [CanTiming; 9] = [
+    CanTiming::new(100, 5, 8, 2, 1), // 10 kbit/s
+    CanTiming::new(50, 5, 8, 2, 1),  // 20 kbit/s
+    CanTiming::new(20, 5, 8, 2, 1),  // 50 kbit/s
+    CanTiming::new(10, 5, 8, 2, 1),  // 100 kbit/s
+    CanTiming::new(8, 5, 8, 2, 1),   // 125 kbit/s
+    CanTiming::new(4, 5, 8, 2, 1),   // 250 kbit/s
+    CanTiming::new(2, 5, 8, 2, 1),   // 500 kbit/s
+    CanTiming::new(2, 1, 7, 1, 1),   // 800 kbit/s
+    CanTiming::new(1, 5, 8, 2, 1),   // 1000 kbit/s
+];
+
+/// Timings for a 24 MHz clock, indexed by [`Bitrate`].
+const CLOCK_24MHZ: [CanTiming; 9] = [
+    CanTiming::new(150, 5, 8, 2, 1), // 10 kbit/s
+    CanTiming::new(75, 5, 8, 2, 1),  // 20 kbit/s
+    CanTiming::new(30, 5, 8, 2, 1),  // 50 kbit/s
+    CanTiming::new(15, 5, 8, 2, 1),  // 100 kbit/s
+    CanTiming::new(12, 5, 8, 2, 1),  // 125 kbit/s
+    CanTiming::new(6, 5, 8, 2, 1),   // 250 kbit/s
+    CanTiming::new(3, 5, 8, 2, 1),   // 500 kbit/s
+    CanTiming::new(2, 4, 8, 2, 1),   // 800 kbit/s
+    CanTiming::new(3, 1, 5, 1, 1),   // 1000 kbit/s
+];
+
+/// Timings for a 48 MHz clock, indexed by [`Bitrate`].
+const CLOCK_48MHZ: [CanTiming; 9] = [
+    CanTiming::new(300, 5, 8, 2, 1), // 10 kbit/s
+    CanTiming::new(150, 5, 8, 2, 1), // 20 kbit/s
+    CanTiming::new(60, 5, 8, 2, 1),  // 50 kbit/s
+    CanTiming::new(30, 5, 8, 2, 1),  // 100 kbit/s
+    CanTiming::new(24, 5, 8, 2, 1),  // 125 kbit/s
+    CanTiming::new(12, 5, 8, 2, 1),  // 250 kbit/s
+    CanTiming::new(6, 5, 8, 2, 1),   // 500 kbit/s
+    CanTiming::new(4, 4, 8, 2, 1),   // 800 kbit/s
+    CanTiming::new(3, 5, 8, 2, 1),   // 1000 kbit/s
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bus_load() {
+        // 1000 frames of 8 bytes at 500 kbit/s is 108000 bits per second.
+        let load = estimate_bus_load(1000.0, 8.0, Bitrate::Rate500kbit);
+        assert!((load - 0.216).abs() < 1e-6);
+
+        assert_eq!(estimate_bus_load(0.0, 8.0, Bitrate::Rate125kbit), 0.0);
+        assert!(estimate_bus_load(10_000.0, 8.0, Bitrate::Rate125kbit) > 1.0);
+    }
+
+    #[test]
+    fn time_to_transmit() {
+        use embedded_can::{ExtendedId, StandardId};
+
+        // 97 bits, 121.25 with stuffing, at 2 us per bit.
+        let frame = Frame::new(StandardId::ZERO, &[0; 8]).unwrap();
+        assert_eq!(frame.time_to_transmit_us(Bitrate::Rate500kbit), 243);
+        assert_eq!(frame.time_to_transmit_us(Bitrate::Rate1000kbit), 122);
+
+        // 115 bits, 143.75 with stuffing.
+        let frame = Frame::new(ExtendedId::ZERO, &[0; 8]).unwrap();
+        assert_eq!(frame.time_to_transmit_us(Bitrate::Rate1000kbit), 144);
+
+        // 33 bits, 41.25 with stuffing, at 100 us per bit.
+        let frame = Frame::new_remote(StandardId::ZERO, 8).unwrap();
+        assert_eq!(frame.time_to_transmit_us(Bitrate::Rate10kbit), 4125);
+    }
+
+    #[test]
+    fn bitrate_and_sample_point() {
+        // 500 kbit/s from 16 MHz with 16 time quanta.
+        let timing = CanTiming::new(2, 1, 12, 2, 1);
+
+        assert!(timing.is_valid());
+        assert_eq!(timing.time_quanta(), 16);
+        assert_eq!(timing.nominal_bitrate(16_000_000), 500_000);
+        assert_eq!(timing.sample_point_percent(), 87.5);
+        assert_eq!(timing.sample_point_permille(), 875);
+    }
+
+    #[test]
+    fn precomputed_tables() {
+        let bitrates = [
+            (Bitrate::Rate10kbit, 10_000),
+            (Bitrate::Rate20kbit, 20_000),
+            (Bitrate::Rate50kbit, 50_000),
+            (Bitrate::Rate100kbit, 100_000),
+            (Bitrate::Rate125kbit, 125_000),
+            (Bitrate::Rate250kbit, 250_000),
+            (Bitrate::Rate500kbit, 500_000),
+            (Bitrate::Rate800kbit, 800_000),
+            (Bitrate::Rate1000kbit, 1_000_000),
+        ];
+
+        for clock_hz in [8_000_000, 16_000_000, 24_000_000, 48_000_000] {
+            for (bitrate, bps) in bitrates {
+                let timing = bitrate.to_can_timing(clock_hz).unwrap();
+                assert!(timing.is_valid());
+                assert_eq!(timing.nominal_bitrate(clock_hz), bps);
+            }
+        }
+
+        assert_eq!(Bitrate::Rate500kbit.to_can_timing(42_000_000), None);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(!CanTiming::new(0, 1, 12, 2, 1).is_valid());
+        assert!(!CanTiming::new(1, 1, 12, 0, 1).is_valid());
+        assert!(!CanTiming::new(1, 1, 12, 2, 3).is_valid());
+        assert!(!CanTiming::new(1, 0, 2, 2, 1).is_valid());
+        assert!(!CanTiming::new(1, 8, 16, 2, 1).is_valid());
+        assert_eq!(
+            CanTiming::new(0, 1, 12, 2, 1).nominal_bitrate(16_000_000),
+            0
+        );
+    }
+}